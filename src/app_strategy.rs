@@ -5,9 +5,27 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use crate::HomeDirError;
+use crate::environment::ProcessEnvironment;
 
 /// The arguments to the creator method of an [`AppStrategy`](trait.AppStrategy.html).
+///
+/// With the `serde` feature enabled, these can be read from a file, such as your packaging manifest:
+///
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// use etcetera::app_strategy::AppStrategyArgs;
+///
+/// let strategy_args: AppStrategyArgs = serde_json::from_str(r#"{
+///     "top_level_domain": "org",
+///     "author": "Acme Corp",
+///     "app_name": "Frobnicator Plus"
+/// }"#).unwrap();
+///
+/// assert_eq!(strategy_args.bundle_id(), "org.acme-corp.Frobnicator-Plus");
+/// # }
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AppStrategyArgs {
     /// The top level domain of the application, e.g. `com`, `org`, or `io.github`.
     pub top_level_domain: String,
@@ -61,6 +79,16 @@ impl AppStrategyArgs {
     }
 }
 
+macro_rules! find_all_method {
+    ($self: ident, $path_extra: expr, $dir_method_name: ident, $dirs_method_name: ident) => {{
+        let path_extra = PathBuf::from(&$path_extra);
+        std::iter::once($self.$dir_method_name())
+            .chain($self.$dirs_method_name())
+            .map(move |dir| dir.join(&path_extra))
+            .filter(|path| path.exists())
+    }};
+}
+
 macro_rules! in_dir_method {
     ($self: ident, $path_extra: expr, $dir_method_name: ident) => {{
         let mut path = $self.$dir_method_name();
@@ -75,6 +103,29 @@ macro_rules! in_dir_method {
 }
 
 /// Allows applications to retrieve the paths of configuration, data, and cache directories specifically for them.
+///
+/// The strategy can be stored as a trait object, which is useful when it is chosen at runtime. The methods that are generic over a path are still available on a `Box<dyn AppStrategy>`, `Arc<dyn AppStrategy>` or `&dyn AppStrategy`, as these implement the trait too.
+///
+/// ```
+/// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Unix, Xdg};
+///
+/// let args = AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// };
+///
+/// let app_strategy: Box<dyn AppStrategy> = if std::env::var_os("FROBNICATOR_USE_UNIX").is_some() {
+///     Box::new(Unix::new(args).unwrap())
+/// } else {
+///     Box::new(Xdg::new(args).unwrap())
+/// };
+///
+/// assert_eq!(
+///     app_strategy.in_config_dir("config.toml"),
+///     app_strategy.config_dir().join("config.toml")
+/// );
+/// ```
 pub trait AppStrategy {
     /// Gets the home directory of the current user.
     fn home_dir(&self) -> &Path;
@@ -89,53 +140,227 @@ pub trait AppStrategy {
     fn cache_dir(&self) -> PathBuf;
 
     /// Gets the state directory for your application.
-    /// Currently, only the [`Xdg`](struct.Xdg.html) & [`Unix`](struct.Unix.html) strategies support
-    /// this.
+    /// Currently, the [`Xdg`](struct.Xdg.html), [`Unix`](struct.Unix.html), [`Flatpak`](struct.Flatpak.html), [`Snap`](struct.Snap.html), [`Portable`](struct.Portable.html) and [`System`](struct.System.html) strategies support this, and the strategies that wrap another one, such as [`SystemdService`](struct.SystemdService.html), forward it; the Apple and Windows strategies return `None`.
     fn state_dir(&self) -> Option<PathBuf>;
 
     /// Gets the runtime directory for your application.
-    /// Currently, only the [`Xdg`](struct.Xdg.html) & [`Unix`](struct.Unix.html) strategies support
-    /// this.
+    /// Currently, the [`Xdg`](struct.Xdg.html), [`Unix`](struct.Unix.html), [`Flatpak`](struct.Flatpak.html), [`Snap`](struct.Snap.html), [`Portable`](struct.Portable.html) and [`System`](struct.System.html) strategies support this, and the strategies that wrap another one, such as [`SystemdService`](struct.SystemdService.html), forward it; the Apple and Windows strategies return `None`.
     ///
     /// Note: The [XDG Base Directory Specification](spec) places additional requirements on this
-    /// directory related to ownership, permissions, and persistence. This library does not check
-    /// these requirements.
+    /// directory related to ownership, permissions, and persistence. This method does not check
+    /// these requirements; on Unix, [`Xdg::checked_runtime_dir`](struct.Xdg.html#method.checked_runtime_dir)
+    /// checks the ones that can be.
     ///
     /// [spec]: https://specifications.freedesktop.org/basedir/latest/
     fn runtime_dir(&self) -> Option<PathBuf>;
 
+    /// Gets the directory for your application’s executables.
+    ///
+    /// Note: Some strategies, such as [`Xdg`](struct.Xdg.html), share this directory between all applications, so that it only has to be added to `PATH` once.
+    ///
+    /// This defaults to `~/.local/bin/`, the directory that the [`Xdg`](../base_strategy/struct.Xdg.html) base strategy uses.
+    fn bin_dir(&self) -> PathBuf {
+        self.home_dir().join(".local/bin/")
+    }
+
+    /// Returns whether the directory for your application’s executables is listed in the `PATH` environment variable.
+    ///
+    /// If it isn’t, executables that you install there can’t be run by name.
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &ProcessEnvironment)
+    }
+
+    /// Gets the system-wide configuration directories for your application, in order of decreasing precedence.
+    /// Currently, only the [`Xdg`](struct.Xdg.html) and [`Flatpak`](struct.Flatpak.html) strategies support this, and the strategies that wrap another one, such as [`SystemdService`](struct.SystemdService.html), forward it; all other strategies return an empty list.
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Gets the system-wide data directories for your application, in order of decreasing precedence.
+    /// Currently, only the [`Xdg`](struct.Xdg.html) and [`Flatpak`](struct.Flatpak.html) strategies support this, and the strategies that wrap another one, such as [`SystemdService`](struct.SystemdService.html), forward it; all other strategies return an empty list.
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
     /// Constructs a path inside your application’s configuration directory to which a path of your choice has been appended.
-    fn in_config_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
+    fn in_config_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf
+    where
+        Self: Sized,
+    {
         in_dir_method!(self, path, config_dir)
     }
 
     /// Constructs a path inside your application’s data directory to which a path of your choice has been appended.
-    fn in_data_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
+    fn in_data_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf
+    where
+        Self: Sized,
+    {
         in_dir_method!(self, path, data_dir)
     }
 
     /// Constructs a path inside your application’s cache directory to which a path of your choice has been appended.
-    fn in_cache_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf {
+    fn in_cache_dir<P: AsRef<OsStr>>(&self, path: P) -> PathBuf
+    where
+        Self: Sized,
+    {
         in_dir_method!(self, path, cache_dir)
     }
 
     /// Constructs a path inside your application’s state directory to which a path of your choice has been appended.
     ///
-    /// Currently, this is only implemented for the [`Xdg`](struct.Xdg.html) strategy.
-    fn in_state_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf> {
+    /// See [`state_dir`](#tymethod.state_dir) for the strategies that support this.
+    fn in_state_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf>
+    where
+        Self: Sized,
+    {
         in_dir_method!(opt: self, path, state_dir)
     }
 
     /// Constructs a path inside your application’s runtime directory to which a path of your choice has been appended.
-    /// Currently, only the [`Xdg`](struct.Xdg.html) & [`Unix`](struct.Unix.html) strategies support
-    /// this.
     ///
-    /// See the note in [`runtime_dir`](#method.runtime_dir) for more information.
-    fn in_runtime_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf> {
+    /// See [`runtime_dir`](#tymethod.runtime_dir) for the strategies that support this, and for a note on the requirements of the runtime directory.
+    fn in_runtime_dir<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf>
+    where
+        Self: Sized,
+    {
         in_dir_method!(opt: self, path, runtime_dir)
     }
+
+    /// Finds a path of your choice inside your application’s configuration directories.
+    ///
+    /// The user’s configuration directory is searched first, followed by each of the [system-wide ones](#method.config_dirs). The first path that exists is returned.
+    /// Strategies without system-wide configuration directories only search the user’s configuration directory.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    ///
+    /// let user_config_home = std::env::temp_dir().join("etcetera-find-config-file-user");
+    /// let system_config_dir = std::env::temp_dir().join("etcetera-find-config-file-system");
+    ///
+    /// let env = MapEnvironment::new(std::env::temp_dir())
+    ///     .with_var("XDG_CONFIG_HOME", &user_config_home)
+    ///     .with_var("XDG_CONFIG_DIRS", &system_config_dir);
+    ///
+    /// let app_strategy = Xdg::with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator".to_string(),
+    /// }, env).unwrap();
+    ///
+    /// std::fs::create_dir_all(system_config_dir.join("frobnicator")).unwrap();
+    /// std::fs::write(system_config_dir.join("frobnicator/config.toml"), "").unwrap();
+    ///
+    /// // Only the system-wide file exists.
+    /// assert_eq!(
+    ///     app_strategy.find_config_file("config.toml"),
+    ///     Some(system_config_dir.join("frobnicator/config.toml"))
+    /// );
+    ///
+    /// std::fs::create_dir_all(user_config_home.join("frobnicator")).unwrap();
+    /// std::fs::write(user_config_home.join("frobnicator/config.toml"), "").unwrap();
+    ///
+    /// // The user’s file takes precedence.
+    /// assert_eq!(
+    ///     app_strategy.find_config_file("config.toml"),
+    ///     Some(user_config_home.join("frobnicator/config.toml"))
+    /// );
+    /// assert_eq!(app_strategy.find_all_config_files("config.toml").count(), 2);
+    /// assert_eq!(app_strategy.find_config_file("missing.toml"), None);
+    ///
+    /// std::fs::remove_dir_all(user_config_home).unwrap();
+    /// std::fs::remove_dir_all(system_config_dir).unwrap();
+    /// ```
+    fn find_config_file<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf>
+    where
+        Self: Sized,
+    {
+        self.find_all_config_files(path).next()
+    }
+
+    /// Finds a path of your choice inside your application’s data directories.
+    ///
+    /// The user’s data directory is searched first, followed by each of the [system-wide ones](#method.data_dirs). The first path that exists is returned.
+    fn find_data_file<P: AsRef<OsStr>>(&self, path: P) -> Option<PathBuf>
+    where
+        Self: Sized,
+    {
+        self.find_all_data_files(path).next()
+    }
+
+    /// Finds every occurrence of a path of your choice inside your application’s configuration directories, in order of decreasing precedence.
+    ///
+    /// See [`find_config_file`](#method.find_config_file) for the order in which the directories are searched.
+    fn find_all_config_files<P: AsRef<OsStr>>(&self, path: P) -> impl Iterator<Item = PathBuf>
+    where
+        Self: Sized,
+    {
+        find_all_method!(self, path.as_ref(), config_dir, config_dirs)
+    }
+
+    /// Finds every occurrence of a path of your choice inside your application’s data directories, in order of decreasing precedence.
+    ///
+    /// See [`find_data_file`](#method.find_data_file) for the order in which the directories are searched.
+    fn find_all_data_files<P: AsRef<OsStr>>(&self, path: P) -> impl Iterator<Item = PathBuf>
+    where
+        Self: Sized,
+    {
+        find_all_method!(self, path.as_ref(), data_dir, data_dirs)
+    }
+}
+
+macro_rules! impl_app_strategy_for_pointer {
+    ($($pointer: ty),*) => {
+        $(
+            impl<T: AppStrategy + ?Sized> AppStrategy for $pointer {
+                fn home_dir(&self) -> &Path {
+                    (**self).home_dir()
+                }
+
+                fn config_dir(&self) -> PathBuf {
+                    (**self).config_dir()
+                }
+
+                fn data_dir(&self) -> PathBuf {
+                    (**self).data_dir()
+                }
+
+                fn cache_dir(&self) -> PathBuf {
+                    (**self).cache_dir()
+                }
+
+                fn state_dir(&self) -> Option<PathBuf> {
+                    (**self).state_dir()
+                }
+
+                fn runtime_dir(&self) -> Option<PathBuf> {
+                    (**self).runtime_dir()
+                }
+
+                fn bin_dir(&self) -> PathBuf {
+                    (**self).bin_dir()
+                }
+
+                fn is_bin_dir_in_path(&self) -> bool {
+                    (**self).is_bin_dir_in_path()
+                }
+
+                fn config_dirs(&self) -> Vec<PathBuf> {
+                    (**self).config_dirs()
+                }
+
+                fn data_dirs(&self) -> Vec<PathBuf> {
+                    (**self).data_dirs()
+                }
+            }
+        )*
+    };
 }
 
+impl_app_strategy_for_pointer!(&T, Box<T>, std::sync::Arc<T>);
+
 macro_rules! create_strategies {
     ($native: ty, $app: ty) => {
         /// Returns the current OS’s native [`AppStrategy`](trait.AppStrategy.html).
@@ -164,12 +389,42 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Returns the current OS’s default [`AppStrategy`](trait.AppStrategy.html) like [`choose_app_strategy`](fn.choose_app_strategy.html), unless the application has been installed in a way that calls for another one.
+///
+/// See [`AnyAppStrategy::detect`](enum.AnyAppStrategy.html#method.detect) for the installations that are detected.
+pub fn detect_app_strategy(args: AppStrategyArgs) -> Result<AnyAppStrategy, HomeDirError> {
+    let kind = if cfg!(target_os = "windows") {
+        AppStrategyKind::Windows
+    } else {
+        AppStrategyKind::Xdg
+    };
+    AnyAppStrategy::detect(kind, args)
+}
+
+mod any;
 mod apple;
+mod apple_machine;
+mod env_override;
+mod flatpak;
+mod portable;
+mod snap;
+mod system;
+mod systemd_service;
 mod unix;
 mod windows;
+mod windows_machine;
 mod xdg;
 
+pub use any::{AnyAppStrategy, AppStrategyKind};
 pub use apple::Apple;
+pub use apple_machine::AppleMachine;
+pub use env_override::EnvOverride;
+pub use flatpak::Flatpak;
+pub use portable::Portable;
+pub use snap::Snap;
+pub use system::System;
+pub use systemd_service::SystemdService;
 pub use unix::Unix;
 pub use windows::Windows;
+pub use windows_machine::WindowsMachine;
 pub use xdg::Xdg;
@@ -0,0 +1,286 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::{
+    AppStrategy, AppStrategyArgs, Apple, AppleMachine, Flatpak, Portable, Snap, System, Unix,
+    Windows, WindowsMachine, Xdg,
+};
+use crate::{HomeDirError, ParseStrategyKindError};
+
+/// The name of an [`AppStrategy`](trait.AppStrategy.html), which lets you choose one at runtime, e.g. from a command-line flag or an environment variable.
+///
+/// These are parsed from, and displayed as, `xdg`, `unix`, `apple`, `windows`, `flatpak`, `snap`, `portable`, `system`, `apple-machine` and `windows-machine`. Parsing ignores case.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategyKind;
+///
+/// assert_eq!("unix".parse(), Ok(AppStrategyKind::Unix));
+/// assert_eq!("XDG".parse(), Ok(AppStrategyKind::Xdg));
+///
+/// let err = "dos".parse::<AppStrategyKind>().unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "unknown strategy `dos`, expected one of: xdg, unix, apple, windows, flatpak, snap, portable, system, apple-machine, windows-machine"
+/// );
+///
+/// assert_eq!(AppStrategyKind::Apple.to_string(), "apple");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AppStrategyKind {
+    /// The [`Xdg`](struct.Xdg.html) strategy.
+    Xdg,
+    /// The [`Unix`](struct.Unix.html) strategy.
+    Unix,
+    /// The [`Apple`](struct.Apple.html) strategy.
+    Apple,
+    /// The [`Windows`](struct.Windows.html) strategy.
+    Windows,
+    /// The [`Flatpak`](struct.Flatpak.html) strategy.
+    Flatpak,
+    /// The [`Snap`](struct.Snap.html) strategy.
+    Snap,
+    /// The [`Portable`](struct.Portable.html) strategy.
+    Portable,
+    /// The [`System`](struct.System.html) strategy.
+    System,
+    /// The [`AppleMachine`](struct.AppleMachine.html) strategy.
+    AppleMachine,
+    /// The [`WindowsMachine`](struct.WindowsMachine.html) strategy.
+    WindowsMachine,
+}
+
+impl AppStrategyKind {
+    /// All of the kinds of app strategies.
+    pub const ALL: [AppStrategyKind; 10] = [
+        Self::Xdg,
+        Self::Unix,
+        Self::Apple,
+        Self::Windows,
+        Self::Flatpak,
+        Self::Snap,
+        Self::Portable,
+        Self::System,
+        Self::AppleMachine,
+        Self::WindowsMachine,
+    ];
+
+    /// The name of this kind of strategy, e.g. `xdg`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Xdg => "xdg",
+            Self::Unix => "unix",
+            Self::Apple => "apple",
+            Self::Windows => "windows",
+            Self::Flatpak => "flatpak",
+            Self::Snap => "snap",
+            Self::Portable => "portable",
+            Self::System => "system",
+            Self::AppleMachine => "apple-machine",
+            Self::WindowsMachine => "windows-machine",
+        }
+    }
+}
+
+impl fmt::Display for AppStrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AppStrategyKind {
+    type Err = ParseStrategyKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseStrategyKindError::new(s, Self::ALL.map(Self::as_str)))
+    }
+}
+
+/// Any of the built-in [`AppStrategy`](trait.AppStrategy.html)s, chosen at runtime.
+///
+/// The strategies that wrap another one, [`SystemdService`](struct.SystemdService.html) and [`EnvOverride`](struct.EnvOverride.html), aren’t included, but they can wrap an `AnyAppStrategy` instead.
+///
+/// This example lets an environment variable override the default strategy:
+///
+/// ```
+/// use etcetera::app_strategy::{AnyAppStrategy, AppStrategy, AppStrategyArgs, AppStrategyKind};
+/// use etcetera::environment::{Environment, MapEnvironment};
+/// use std::path::Path;
+///
+/// let env = MapEnvironment::without_home_dir().with_var("FROBNICATOR_DIR_LAYOUT", "unix");
+///
+/// let kind = match env.var_os("FROBNICATOR_DIR_LAYOUT") {
+///     Some(layout) => layout.to_str().unwrap().parse().unwrap(),
+///     None => AppStrategyKind::Xdg,
+/// };
+///
+/// let app_strategy = AnyAppStrategy::new(kind, AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }).unwrap();
+///
+/// assert_eq!(app_strategy.kind(), AppStrategyKind::Unix);
+/// assert_eq!(
+///     app_strategy.config_dir().strip_prefix(app_strategy.home_dir()),
+///     Ok(Path::new(".frobnicator-plus"))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnyAppStrategy {
+    /// The [`Xdg`](struct.Xdg.html) strategy.
+    Xdg(Xdg),
+    /// The [`Unix`](struct.Unix.html) strategy.
+    Unix(Unix),
+    /// The [`Apple`](struct.Apple.html) strategy.
+    Apple(Apple),
+    /// The [`Windows`](struct.Windows.html) strategy.
+    Windows(Windows),
+    /// The [`Flatpak`](struct.Flatpak.html) strategy.
+    Flatpak(Flatpak),
+    /// The [`Snap`](struct.Snap.html) strategy.
+    Snap(Snap),
+    /// The [`Portable`](struct.Portable.html) strategy.
+    Portable(Portable),
+    /// The [`System`](struct.System.html) strategy.
+    System(System),
+    /// The [`AppleMachine`](struct.AppleMachine.html) strategy.
+    AppleMachine(AppleMachine),
+    /// The [`WindowsMachine`](struct.WindowsMachine.html) strategy.
+    WindowsMachine(WindowsMachine),
+}
+
+impl AnyAppStrategy {
+    /// Create a new AppStrategy of the given kind
+    pub fn new(kind: AppStrategyKind, args: AppStrategyArgs) -> Result<Self, HomeDirError> {
+        Ok(match kind {
+            AppStrategyKind::Xdg => Self::Xdg(Xdg::new(args)?),
+            AppStrategyKind::Unix => Self::Unix(Unix::new(args)?),
+            AppStrategyKind::Apple => Self::Apple(Apple::new(args)?),
+            AppStrategyKind::Windows => Self::Windows(Windows::new(args)?),
+            AppStrategyKind::Flatpak => Self::Flatpak(Flatpak::new(args)?),
+            AppStrategyKind::Snap => Self::Snap(Snap::new(args)?),
+            AppStrategyKind::Portable => Self::Portable(Portable::new(args)),
+            AppStrategyKind::System => Self::System(System::new(args)),
+            AppStrategyKind::AppleMachine => Self::AppleMachine(AppleMachine::new(args)),
+            AppStrategyKind::WindowsMachine => Self::WindowsMachine(WindowsMachine::new(args)),
+        })
+    }
+
+    /// Create a new AppStrategy for the way that the application has been installed, or one of the given kind if it has been installed normally
+    ///
+    /// Currently, this detects [Flatpak](struct.Flatpak.html) sandboxes and [snaps](struct.Snap.html) on Linux. It only fails when a strategy of the given kind can’t be created, so kinds like [`System`](struct.System.html) still work without a home directory.
+    pub fn detect(kind: AppStrategyKind, args: AppStrategyArgs) -> Result<Self, HomeDirError> {
+        // Flatpak and snapd only exist on Linux. Their strategies need a home directory, unlike some of the kinds that may have been asked for, so they are only created once there is a sign of them.
+        #[cfg(target_os = "linux")]
+        {
+            let is_set = |env_var| std::env::var_os(env_var).is_some_and(|value| !value.is_empty());
+
+            if std::fs::symlink_metadata("/.flatpak-info").is_ok() || is_set("FLATPAK_ID") {
+                if let Ok(flatpak) = Flatpak::new(args.clone()) {
+                    if flatpak.is_sandboxed() {
+                        return Ok(Self::Flatpak(flatpak));
+                    }
+                }
+            }
+
+            if is_set("SNAP_USER_DATA") {
+                if let Ok(snap) = Snap::new(args.clone()) {
+                    return Ok(Self::Snap(snap));
+                }
+            }
+        }
+
+        Self::new(kind, args)
+    }
+
+    /// Create a new AppStrategy like [`detect`](#method.detect), but use the [`Portable`](struct.Portable.html) strategy instead if it has been [enabled](struct.Portable.html#method.is_enabled) next to the executable
+    pub fn detect_portable(
+        kind: AppStrategyKind, args: AppStrategyArgs,
+    ) -> Result<Self, HomeDirError> {
+        let portable = Portable::new(args.clone());
+        if portable.is_enabled() {
+            return Ok(Self::Portable(portable));
+        }
+
+        Self::detect(kind, args)
+    }
+
+    /// Gets the kind of this strategy.
+    pub fn kind(&self) -> AppStrategyKind {
+        match self {
+            Self::Xdg(_) => AppStrategyKind::Xdg,
+            Self::Unix(_) => AppStrategyKind::Unix,
+            Self::Apple(_) => AppStrategyKind::Apple,
+            Self::Windows(_) => AppStrategyKind::Windows,
+            Self::Flatpak(_) => AppStrategyKind::Flatpak,
+            Self::Snap(_) => AppStrategyKind::Snap,
+            Self::Portable(_) => AppStrategyKind::Portable,
+            Self::System(_) => AppStrategyKind::System,
+            Self::AppleMachine(_) => AppStrategyKind::AppleMachine,
+            Self::WindowsMachine(_) => AppStrategyKind::WindowsMachine,
+        }
+    }
+}
+
+macro_rules! delegate {
+    ($self: ident, $strategy: ident => $expr: expr) => {
+        match $self {
+            AnyAppStrategy::Xdg($strategy) => $expr,
+            AnyAppStrategy::Unix($strategy) => $expr,
+            AnyAppStrategy::Apple($strategy) => $expr,
+            AnyAppStrategy::Windows($strategy) => $expr,
+            AnyAppStrategy::Flatpak($strategy) => $expr,
+            AnyAppStrategy::Snap($strategy) => $expr,
+            AnyAppStrategy::Portable($strategy) => $expr,
+            AnyAppStrategy::System($strategy) => $expr,
+            AnyAppStrategy::AppleMachine($strategy) => $expr,
+            AnyAppStrategy::WindowsMachine($strategy) => $expr,
+        }
+    };
+}
+
+impl AppStrategy for AnyAppStrategy {
+    fn home_dir(&self) -> &Path {
+        delegate!(self, strategy => strategy.home_dir())
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        delegate!(self, strategy => strategy.config_dir())
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        delegate!(self, strategy => strategy.data_dir())
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        delegate!(self, strategy => strategy.cache_dir())
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        delegate!(self, strategy => strategy.state_dir())
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        delegate!(self, strategy => strategy.runtime_dir())
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        delegate!(self, strategy => strategy.bin_dir())
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        delegate!(self, strategy => strategy.is_bin_dir_in_path())
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        delegate!(self, strategy => strategy.config_dirs())
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        delegate!(self, strategy => strategy.data_dirs())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::base_strategy::BaseStrategy;
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
 use crate::{HomeDirError, base_strategy};
 use std::path::{Path, PathBuf};
 
@@ -42,24 +43,71 @@ use std::path::{Path, PathBuf};
 ///     app_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     app_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/bin/"))
+/// );
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Apple {
-    base_strategy: base_strategy::Apple,
+pub struct Apple<E = ProcessEnvironment> {
+    base_strategy: base_strategy::Apple<E>,
     bundle_id: String,
 }
 
 impl Apple {
     /// Create a new Apple AppStrategy
     pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+        Self::with_env(args, ProcessEnvironment)
+    }
+}
+
+impl Apple<MapEnvironment> {
+    /// Create a new Apple AppStrategy for the given home directory instead of the current user’s one
+    ///
+    /// The environment of the current process isn’t read.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Apple;
+    /// use std::path::Path;
+    ///
+    /// let app_strategy = Apple::with_home(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, "/Users/frobnicator");
+    ///
+    /// assert_eq!(
+    ///     app_strategy.config_dir(),
+    ///     Path::new("/Users/frobnicator/Library/Preferences/org.acme-corp.Frobnicator-Plus")
+    /// );
+    /// ```
+    pub fn with_home(args: super::AppStrategyArgs, home_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_strategy: base_strategy::Apple::with_home(home_dir),
+            bundle_id: args.bundle_id(),
+        }
+    }
+}
+
+impl<E: Environment> Apple<E> {
+    /// Create a new Apple AppStrategy that reads from the given environment instead of the current process’s one
+    ///
+    /// The directories only depend on the environment’s home directory, but its `PATH` is used by [`is_bin_dir_in_path`](trait.AppStrategy.html#method.is_bin_dir_in_path).
+    pub fn with_env(args: super::AppStrategyArgs, env: E) -> Result<Self, HomeDirError> {
         Ok(Self {
-            base_strategy: base_strategy::Apple::new()?,
+            base_strategy: base_strategy::Apple::with_env(env)?,
             bundle_id: args.bundle_id(),
         })
     }
+
+    pub(crate) fn bundle_id(&self) -> &str {
+        &self.bundle_id
+    }
 }
 
-impl super::AppStrategy for Apple {
+impl<E: Environment> super::AppStrategy for Apple<E> {
     fn home_dir(&self) -> &Path {
         self.base_strategy.home_dir()
     }
@@ -83,4 +131,12 @@ impl super::AppStrategy for Apple {
     fn runtime_dir(&self) -> Option<PathBuf> {
         None
     }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy.bin_dir()
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        self.base_strategy.is_bin_dir_in_path()
+    }
 }
@@ -0,0 +1,105 @@
+use crate::base_strategy;
+use crate::base_strategy::BaseStrategy;
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
+use std::path::{Path, PathBuf};
+
+/// This is the machine-wide counterpart to the [`Apple`](struct.Apple.html) strategy, for files that are shared between all users of a computer, e.g. by a launch daemon. It names your application’s directories after its [bundle identifier](struct.AppStrategyArgs.html#method.bundle_id) in the same way, but keeps them in `/Library` instead of the user’s `~/Library`.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::AppleMachine;
+/// use std::path::Path;
+///
+/// let app_strategy = AppleMachine::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// });
+///
+/// assert_eq!(
+///     app_strategy.config_dir(),
+///     Path::new("/Library/Preferences/org.acme-corp.Frobnicator-Plus")
+/// );
+/// assert_eq!(
+///     app_strategy.data_dir(),
+///     Path::new("/Library/Application Support/org.acme-corp.Frobnicator-Plus")
+/// );
+/// assert_eq!(
+///     app_strategy.cache_dir(),
+///     Path::new("/Library/Caches/org.acme-corp.Frobnicator-Plus")
+/// );
+/// assert_eq!(app_strategy.state_dir(), None);
+/// assert_eq!(app_strategy.runtime_dir(), None);
+/// assert_eq!(app_strategy.bin_dir(), Path::new("/usr/local/bin/"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AppleMachine<E = ProcessEnvironment> {
+    base_strategy: base_strategy::AppleMachine<E>,
+    bundle_id: String,
+}
+
+impl AppleMachine {
+    /// Create a new AppleMachine AppStrategy
+    pub fn new(args: super::AppStrategyArgs) -> Self {
+        Self::with_env(args, ProcessEnvironment)
+    }
+}
+
+impl AppleMachine<MapEnvironment> {
+    /// Create a new AppleMachine AppStrategy for the given home directory instead of the current user’s one
+    ///
+    /// The environment of the current process isn’t read.
+    pub fn with_home(args: super::AppStrategyArgs, home_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_strategy: base_strategy::AppleMachine::with_home(home_dir),
+            bundle_id: args.bundle_id(),
+        }
+    }
+}
+
+impl<E: Environment> AppleMachine<E> {
+    /// Create a new AppleMachine AppStrategy that reads from the given environment instead of the current process’s one
+    ///
+    /// The directories don’t depend on the environment, but its `PATH` is used by [`is_bin_dir_in_path`](trait.AppStrategy.html#method.is_bin_dir_in_path).
+    pub fn with_env(args: super::AppStrategyArgs, env: E) -> Self {
+        Self {
+            base_strategy: base_strategy::AppleMachine::with_env(env),
+            bundle_id: args.bundle_id(),
+        }
+    }
+}
+
+impl<E: Environment> super::AppStrategy for AppleMachine<E> {
+    fn home_dir(&self) -> &Path {
+        self.base_strategy.home_dir()
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.base_strategy.config_dir().join(&self.bundle_id)
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.base_strategy.data_dir().join(&self.bundle_id)
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.base_strategy.cache_dir().join(&self.bundle_id)
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy.bin_dir()
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        self.base_strategy.is_bin_dir_in_path()
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::{Path, PathBuf};
+
+use super::AppStrategy;
+use crate::environment::{Environment, ProcessEnvironment};
+
+/// This strategy lets the users of your application move its directories with environment variables, and falls back to another strategy for the ones they haven’t moved.
+///
+/// The variables are named after the ‘[unixy](struct.AppStrategyArgs.html#method.unixy_name)’ name of your application, upper-cased and with `-` replaced by `_`. For Frobnicator Plus, these are `FROBNICATOR_PLUS_CONFIG_DIR`, `FROBNICATOR_PLUS_DATA_DIR`, `FROBNICATOR_PLUS_CACHE_DIR`, `FROBNICATOR_PLUS_STATE_DIR` and `FROBNICATOR_PLUS_RUNTIME_DIR`.
+/// Alternatively, `FROBNICATOR_PLUS_HOME` moves all of them at once, the way `CARGO_HOME` does, and uses the same layout inside it as the [`Unix`](struct.Unix.html) strategy. The variables for single directories take precedence over it.
+///
+/// Like with the [`Xdg`](struct.Xdg.html) strategy, variables whose values aren’t absolute paths are ignored.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::{EnvOverride, Xdg};
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// // We need to conditionally set these to ensure that they are absolute paths both on Windows and other systems.
+/// let home_path = if cfg!(windows) {
+///     "C:\\Users\\frobnicator\\"
+/// } else {
+///     "/home/frobnicator/"
+/// };
+/// let frobnicator_home_path = if cfg!(windows) {
+///     "C:\\frobnicator\\"
+/// } else {
+///     "/frobnicator/"
+/// };
+/// let cache_path = if cfg!(windows) {
+///     "C:\\cache\\"
+/// } else {
+///     "/cache/"
+/// };
+///
+/// let env = MapEnvironment::new(home_path)
+///     .with_var("FROBNICATOR_PLUS_HOME", frobnicator_home_path)
+///     .with_var("FROBNICATOR_PLUS_CACHE_DIR", cache_path)
+///     .with_var("FROBNICATOR_PLUS_RUNTIME_DIR", "relative/runtime");
+///
+/// let args = AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// };
+/// let app_strategy = EnvOverride::with_env(
+///     args.clone(),
+///     Xdg::with_env(args, env.clone()).unwrap(),
+///     env,
+/// );
+///
+/// assert_eq!(app_strategy.config_dir(), Path::new(frobnicator_home_path));
+/// assert_eq!(app_strategy.data_dir(), Path::new(frobnicator_home_path).join("data/"));
+/// assert_eq!(app_strategy.cache_dir(), Path::new(cache_path));
+///
+/// // The runtime directory isn’t absolute, so it is ignored.
+/// assert_eq!(
+///     app_strategy.runtime_dir(),
+///     Some(Path::new(frobnicator_home_path).join("runtime/"))
+/// );
+///
+/// // The home directory itself isn’t affected.
+/// assert_eq!(app_strategy.home_dir(), Path::new(home_path));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EnvOverride<S, E = ProcessEnvironment> {
+    inner: S,
+    env: E,
+    // This is `FROBNICATOR_PLUS` in the above example.
+    env_var_prefix: String,
+}
+
+impl<S: AppStrategy> EnvOverride<S> {
+    /// Create a new EnvOverride AppStrategy that falls back to the given strategy
+    pub fn new(args: super::AppStrategyArgs, inner: S) -> Self {
+        Self::with_env(args, inner, ProcessEnvironment)
+    }
+}
+
+impl<S: AppStrategy, E: Environment> EnvOverride<S, E> {
+    /// Create a new EnvOverride AppStrategy that falls back to the given strategy, and reads from the given environment instead of the current process’s one
+    pub fn with_env(args: super::AppStrategyArgs, inner: S, env: E) -> Self {
+        Self {
+            inner,
+            env,
+            env_var_prefix: args.unixy_name().to_uppercase().replace('-', "_"),
+        }
+    }
+
+    /// Gets the strategy that is used for the directories that haven’t been overridden.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn env_var_or_none(&self, suffix: &str) -> Option<PathBuf> {
+        let env_var = format!("{}_{suffix}", self.env_var_prefix);
+        self.env.var_os(&env_var).and_then(|path| {
+            let path = PathBuf::from(path);
+
+            // Return None if the path obtained from the environment variable isn’t absolute.
+            if path.is_absolute() { Some(path) } else { None }
+        })
+    }
+
+    fn override_dir(&self, suffix: &str, home_subdir: &str) -> Option<PathBuf> {
+        self.env_var_or_none(suffix).or_else(|| {
+            self.env_var_or_none("HOME")
+                .map(|home| home.join(home_subdir))
+        })
+    }
+}
+
+impl<S: AppStrategy, E: Environment> AppStrategy for EnvOverride<S, E> {
+    fn home_dir(&self) -> &Path {
+        self.inner.home_dir()
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.override_dir("CONFIG_DIR", "")
+            .unwrap_or_else(|| self.inner.config_dir())
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.override_dir("DATA_DIR", "data/")
+            .unwrap_or_else(|| self.inner.data_dir())
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.override_dir("CACHE_DIR", "cache/")
+            .unwrap_or_else(|| self.inner.cache_dir())
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.override_dir("STATE_DIR", "state/")
+            .or_else(|| self.inner.state_dir())
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.override_dir("RUNTIME_DIR", "runtime/")
+            .or_else(|| self.inner.runtime_dir())
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.env_var_or_none("HOME")
+            .map(|home| home.join("bin/"))
+            .unwrap_or_else(|| self.inner.bin_dir())
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.env)
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.inner.config_dirs()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.inner.data_dirs()
+    }
+}
@@ -0,0 +1,297 @@
+use crate::base_strategy::BaseStrategy;
+use crate::environment::{Environment, ProcessEnvironment};
+use crate::{HomeDirError, base_strategy};
+use std::path::{Path, PathBuf};
+
+/// This strategy follows the conventions of [Flatpak](https://docs.flatpak.org/en/latest/conventions.html), which gives every application its own directories in `~/.var/app/<app-id>`.
+///
+/// Inside the sandbox, Flatpak points the XDG environment variables at these directories, and the strategy follows them. Outside the sandbox, it locates the same directories from the host, e.g. for a tool that manages an application’s data.
+/// The application ID is read from `FLATPAK_ID` or `/.flatpak-info`, and defaults to the [bundle identifier](struct.AppStrategyArgs.html#method.bundle_id) otherwise.
+/// Like with the [`Xdg`](struct.Xdg.html) strategy, the directories for your application are named after its ‘[unixy](struct.AppStrategyArgs.html#method.unixy_name)’ name, so that its layout inside the sandbox doesn’t change.
+///
+/// This example creates a fake sandbox, as the root that `.flatpak-info` is read from can be chosen:
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::Flatpak;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// let root = std::env::temp_dir().join("etcetera-flatpak");
+/// std::fs::create_dir_all(&root).unwrap();
+/// std::fs::write(
+///     root.join(".flatpak-info"),
+///     "[Application]\nname=org.acme_corp.FrobnicatorPlus\n\n[Context]\nfilesystems=xdg-config:ro;\n",
+/// ).unwrap();
+///
+/// let env = MapEnvironment::new("/home/frobnicator")
+///     .with_var("XDG_CONFIG_HOME", "/home/frobnicator/.var/app/org.acme_corp.FrobnicatorPlus/config");
+///
+/// let app_strategy = Flatpak::with_env_and_root(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }, env, &root).unwrap();
+///
+/// assert!(app_strategy.is_sandboxed());
+/// assert_eq!(app_strategy.app_id(), "org.acme_corp.FrobnicatorPlus");
+/// assert_eq!(
+///     app_strategy.config_dir(),
+///     Path::new("/home/frobnicator/.var/app/org.acme_corp.FrobnicatorPlus/config/frobnicator-plus")
+/// );
+/// assert_eq!(
+///     app_strategy.data_dir(),
+///     Path::new("/home/frobnicator/.var/app/org.acme_corp.FrobnicatorPlus/data/frobnicator-plus")
+/// );
+///
+/// // The application has been given access to the host’s configuration directory, but not to its data directory.
+/// assert_eq!(
+///     app_strategy.host_config_dir(),
+///     Some(Path::new("/home/frobnicator/.config/").to_path_buf())
+/// );
+/// assert_eq!(app_strategy.host_data_dir(), None);
+///
+/// std::fs::remove_dir_all(root).unwrap();
+/// ```
+///
+/// Outside the sandbox, the XDG environment variables belong to the host, so they are ignored:
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::Flatpak;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// let root = std::env::temp_dir().join("etcetera-flatpak-host");
+/// std::fs::create_dir_all(&root).unwrap();
+///
+/// let env = MapEnvironment::new("/home/frobnicator").with_var("XDG_CACHE_HOME", "/cache");
+///
+/// let app_strategy = Flatpak::with_env_and_root(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }, env, &root).unwrap();
+///
+/// assert!(!app_strategy.is_sandboxed());
+/// assert_eq!(app_strategy.app_id(), "org.acme-corp.Frobnicator-Plus");
+/// assert_eq!(
+///     app_strategy.cache_dir(),
+///     Path::new("/home/frobnicator/.var/app/org.acme-corp.Frobnicator-Plus/cache/frobnicator-plus")
+/// );
+/// assert_eq!(
+///     app_strategy.state_dir().unwrap(),
+///     Path::new("/home/frobnicator/.var/app/org.acme-corp.Frobnicator-Plus/.local/state/frobnicator-plus")
+/// );
+/// assert_eq!(app_strategy.runtime_dir(), None);
+///
+/// std::fs::remove_dir_all(root).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Flatpak<E = ProcessEnvironment> {
+    base_strategy: base_strategy::Xdg<E>,
+    unixy_name: String,
+    app_id: String,
+    sandboxed: bool,
+    // The `filesystems` the application has been given access to, e.g. `xdg-config:ro`.
+    filesystems: Vec<String>,
+}
+
+impl Flatpak {
+    /// Create a new Flatpak AppStrategy
+    pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+        Self::with_env(args, ProcessEnvironment)
+    }
+}
+
+impl<E: Environment> Flatpak<E> {
+    /// Create a new Flatpak AppStrategy that reads from the given environment instead of the current process’s one
+    pub fn with_env(args: super::AppStrategyArgs, env: E) -> Result<Self, HomeDirError> {
+        Self::with_env_and_root(args, env, "/")
+    }
+
+    /// Create a new Flatpak AppStrategy that reads from the given environment, and looks for `.flatpak-info` in the given root directory instead of `/`
+    pub fn with_env_and_root(
+        args: super::AppStrategyArgs, env: E, root: impl AsRef<Path>,
+    ) -> Result<Self, HomeDirError> {
+        let base_strategy = base_strategy::Xdg::with_env(env)?;
+        let info_path = root.as_ref().join(".flatpak-info");
+        let sandboxed = std::fs::symlink_metadata(&info_path).is_ok();
+        let info = std::fs::read_to_string(&info_path).unwrap_or_default();
+
+        let app_id = base_strategy
+            .env
+            .var_os("FLATPAK_ID")
+            .and_then(|app_id| app_id.into_string().ok())
+            .filter(|app_id| !app_id.is_empty())
+            .or_else(|| info_value(&info, "Application", "name").map(str::to_string))
+            .unwrap_or_else(|| args.bundle_id());
+        let filesystems = info_value(&info, "Context", "filesystems")
+            .unwrap_or_default()
+            .split(';')
+            .filter(|filesystem| !filesystem.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        Ok(Self {
+            base_strategy,
+            unixy_name: args.unixy_name(),
+            app_id,
+            sandboxed,
+            filesystems,
+        })
+    }
+
+    /// Returns whether the application is running inside a Flatpak sandbox.
+    pub fn is_sandboxed(&self) -> bool {
+        self.sandboxed
+    }
+
+    /// Gets the application’s Flatpak ID, e.g. `org.acme_corp.FrobnicatorPlus`.
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    /// Gets the directory that Flatpak keeps the application’s files in, i.e. `~/.var/app/<app-id>`.
+    pub fn app_dir(&self) -> PathBuf {
+        self.base_strategy
+            .home_dir()
+            .join(".var/app")
+            .join(&self.app_id)
+    }
+
+    /// Gets the host’s configuration directory, if the application can access it.
+    ///
+    /// Inside the sandbox, this requires the `xdg-config`, `home` or `host` filesystem permission, e.g. from `--filesystem=xdg-config`.
+    pub fn host_config_dir(&self) -> Option<PathBuf> {
+        self.host_dir("XDG_CONFIG_HOME", ".config/", "xdg-config")
+    }
+
+    /// Gets the host’s data directory, if the application can access it.
+    ///
+    /// Inside the sandbox, this requires the `xdg-data`, `home` or `host` filesystem permission, e.g. from `--filesystem=xdg-data`.
+    pub fn host_data_dir(&self) -> Option<PathBuf> {
+        self.host_dir("XDG_DATA_HOME", ".local/share/", "xdg-data")
+    }
+
+    fn host_dir(&self, env_var: &str, default: &str, filesystem: &str) -> Option<PathBuf> {
+        if !self.sandboxed {
+            return Some(
+                self.env_var_path(env_var)
+                    .unwrap_or_else(|| self.base_strategy.home_dir().join(default)),
+            );
+        }
+
+        let has_access = self.filesystems.iter().any(|granted| {
+            // Permissions may end in a mode such as `:ro`.
+            let granted = granted.split(':').next().unwrap_or_default();
+            ["host", "home", filesystem].contains(&granted)
+        });
+        if !has_access {
+            return None;
+        }
+
+        // Flatpak passes on the host’s values of the XDG environment variables with a `HOST_` prefix.
+        Some(
+            self.env_var_path(&format!("HOST_{env_var}"))
+                .unwrap_or_else(|| self.base_strategy.home_dir().join(default)),
+        )
+    }
+
+    // Inside the sandbox, the XDG environment variables point into the application’s directory.
+    fn sandbox_dir(&self, env_var: &str, default: &str) -> PathBuf {
+        self.sandboxed
+            .then(|| self.env_var_path(env_var))
+            .flatten()
+            .unwrap_or_else(|| self.app_dir().join(default))
+            .join(&self.unixy_name)
+    }
+
+    fn env_var_path(&self, env_var: &str) -> Option<PathBuf> {
+        self.base_strategy
+            .env
+            .var_os(env_var)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    }
+}
+
+// Reads a value from a file in the key file format that `.flatpak-info` uses.
+fn info_value<'a>(info: &'a str, section: &str, key: &str) -> Option<&'a str> {
+    let mut in_section = false;
+    for line in info.lines().map(str::trim) {
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            in_section = name == section;
+        } else if let Some((line_key, value)) = line.split_once('=') {
+            if in_section && line_key.trim() == key {
+                return Some(value.trim());
+            }
+        }
+    }
+    None
+}
+
+impl<E: Environment> super::AppStrategy for Flatpak<E> {
+    fn home_dir(&self) -> &Path {
+        self.base_strategy.home_dir()
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.sandbox_dir("XDG_CONFIG_HOME", "config")
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.sandbox_dir("XDG_DATA_HOME", "data")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.sandbox_dir("XDG_CACHE_HOME", "cache")
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        Some(self.sandbox_dir("XDG_STATE_HOME", ".local/state"))
+    }
+
+    // Flatpak shares this directory between the sandbox and the host.
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .runtime_dir()
+            .map(|runtime_dir| runtime_dir.join("app").join(&self.app_id))
+    }
+
+    // Executables inside the sandbox can’t be run by the host, so they are kept in the application’s directory.
+    fn bin_dir(&self) -> PathBuf {
+        self.app_dir().join(".local/bin")
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.base_strategy.env)
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        if !self.sandboxed {
+            return Vec::new();
+        }
+        self.base_strategy
+            .config_dirs()
+            .into_iter()
+            .map(|config_dir| config_dir.join(&self.unixy_name))
+            .collect()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        if !self.sandboxed {
+            return Vec::new();
+        }
+        self.base_strategy
+            .data_dirs()
+            .into_iter()
+            .map(|data_dir| data_dir.join(&self.unixy_name))
+            .collect()
+    }
+}
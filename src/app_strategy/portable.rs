@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+/// This strategy keeps all of your application’s directories together in a single root directory, which is next to its executable by default. This lets it be run from a USB stick, or from a directory that has simply been unzipped, without leaving anything behind on the machine.
+///
+/// As the directories can be anywhere, the strategy only switches on once the root contains a `<app>.portable` marker file, where `<app>` is the ‘[unixy](struct.AppStrategyArgs.html#method.unixy_name)’ name of your application. Check for this with [`is_enabled`](#method.is_enabled), or let [`AnyAppStrategy::detect_portable`](enum.AnyAppStrategy.html#method.detect_portable) do it for you.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::Portable;
+/// use std::path::Path;
+///
+/// let root = std::env::temp_dir().join("etcetera-portable");
+/// std::fs::create_dir_all(&root).unwrap();
+///
+/// let app_strategy = Portable::with_root(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }, &root);
+///
+/// assert!(!app_strategy.is_enabled());
+/// std::fs::write(root.join("frobnicator-plus.portable"), "").unwrap();
+/// assert!(app_strategy.is_enabled());
+///
+/// assert_eq!(app_strategy.home_dir(), root);
+/// assert_eq!(app_strategy.config_dir(), root.join("config/"));
+/// assert_eq!(app_strategy.data_dir(), root.join("data/"));
+/// assert_eq!(app_strategy.cache_dir(), root.join("cache/"));
+/// assert_eq!(app_strategy.state_dir().unwrap(), root.join("state/"));
+/// assert_eq!(app_strategy.runtime_dir().unwrap(), root.join("runtime/"));
+/// assert_eq!(app_strategy.bin_dir(), root.join("bin/"));
+///
+/// std::fs::remove_dir_all(root).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Portable {
+    root: PathBuf,
+    unixy_name: String,
+}
+
+impl Portable {
+    /// Create a new Portable AppStrategy rooted in the directory that contains the current executable
+    ///
+    /// If the location of the executable can’t be determined, the current directory is used instead.
+    pub fn new(args: super::AppStrategyArgs) -> Self {
+        let root = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("."));
+        Self::with_root(args, root)
+    }
+
+    /// Create a new Portable AppStrategy rooted in the given directory
+    pub fn with_root(args: super::AppStrategyArgs, root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            unixy_name: args.unixy_name(),
+        }
+    }
+
+    /// Gets the directory that all of the application’s directories are kept in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns whether portable mode has been switched on by a `<app>.portable` file in the root directory.
+    pub fn is_enabled(&self) -> bool {
+        self.root
+            .join(format!("{}.portable", self.unixy_name))
+            .is_file()
+    }
+}
+
+impl super::AppStrategy for Portable {
+    // A portable application shouldn’t depend on the machine it’s running on, so the root stands in for the home directory.
+    fn home_dir(&self) -> &Path {
+        &self.root
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.root.join("config/")
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.root.join("data/")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.root.join("cache/")
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        Some(self.root.join("state/"))
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        Some(self.root.join("runtime/"))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.root.join("bin/")
+    }
+}
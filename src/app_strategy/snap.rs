@@ -0,0 +1,145 @@
+use crate::base_strategy::BaseStrategy;
+use crate::environment::{Environment, ProcessEnvironment};
+use crate::{HomeDirError, base_strategy};
+use std::path::{Path, PathBuf};
+
+/// This strategy follows the conventions of [Snap](https://snapcraft.io/docs/environment-variables) packages.
+///
+/// Inside a snap, `HOME` points to `$SNAP_USER_DATA`, e.g. `~/snap/<snap-name>/<revision>`, which is copied to a new directory every time the snap is refreshed, and is rolled back together with the snap. Only the configuration directory is kept there, so that it matches the revision of the application that wrote it. The data, cache and state directories are kept in `$SNAP_USER_COMMON`, e.g. `~/snap/<snap-name>/common`, which is shared between all revisions of the snap.
+/// Outside a snap, both of these fall back to the home directory, giving the same layout as the [`Xdg`](struct.Xdg.html) strategy without its environment variables.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::Snap;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// let env = MapEnvironment::new("/home/frobnicator/snap/frobnicator/42")
+///     .with_var("SNAP_USER_DATA", "/home/frobnicator/snap/frobnicator/42")
+///     .with_var("SNAP_USER_COMMON", "/home/frobnicator/snap/frobnicator/common")
+///     .with_var("SNAP_REAL_HOME", "/home/frobnicator");
+///
+/// let app_strategy = Snap::with_env(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }, env).unwrap();
+///
+/// assert!(app_strategy.is_in_snap());
+/// assert_eq!(app_strategy.real_home_dir(), Some(Path::new("/home/frobnicator").to_path_buf()));
+/// assert_eq!(
+///     app_strategy.config_dir(),
+///     Path::new("/home/frobnicator/snap/frobnicator/42/.config/frobnicator-plus")
+/// );
+/// assert_eq!(
+///     app_strategy.data_dir(),
+///     Path::new("/home/frobnicator/snap/frobnicator/common/.local/share/frobnicator-plus")
+/// );
+/// assert_eq!(
+///     app_strategy.cache_dir(),
+///     Path::new("/home/frobnicator/snap/frobnicator/common/.cache/frobnicator-plus")
+/// );
+/// assert_eq!(
+///     app_strategy.state_dir().unwrap(),
+///     Path::new("/home/frobnicator/snap/frobnicator/common/.local/state/frobnicator-plus")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snap<E = ProcessEnvironment> {
+    base_strategy: base_strategy::Xdg<E>,
+    unixy_name: String,
+}
+
+impl Snap {
+    /// Create a new Snap AppStrategy
+    pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+        Self::with_env(args, ProcessEnvironment)
+    }
+}
+
+impl<E: Environment> Snap<E> {
+    /// Create a new Snap AppStrategy that reads from the given environment instead of the current process’s one
+    pub fn with_env(args: super::AppStrategyArgs, env: E) -> Result<Self, HomeDirError> {
+        Ok(Self {
+            base_strategy: base_strategy::Xdg::with_env(env)?,
+            unixy_name: args.unixy_name(),
+        })
+    }
+
+    /// Returns whether the application is running inside a snap.
+    pub fn is_in_snap(&self) -> bool {
+        self.env_var_path("SNAP_USER_DATA").is_some()
+    }
+
+    /// Gets the user’s real home directory, rather than the one that the snap has been given.
+    ///
+    /// This is only available inside a snap.
+    pub fn real_home_dir(&self) -> Option<PathBuf> {
+        self.env_var_path("SNAP_REAL_HOME")
+    }
+
+    /// Gets the directory that is specific to the current revision of the snap, i.e. `$SNAP_USER_DATA`.
+    pub fn user_data_dir(&self) -> PathBuf {
+        self.env_var_path("SNAP_USER_DATA")
+            .unwrap_or_else(|| self.base_strategy.home_dir().to_path_buf())
+    }
+
+    /// Gets the directory that is shared between all revisions of the snap, i.e. `$SNAP_USER_COMMON`.
+    pub fn user_common_dir(&self) -> PathBuf {
+        self.env_var_path("SNAP_USER_COMMON")
+            .unwrap_or_else(|| self.base_strategy.home_dir().to_path_buf())
+    }
+
+    // These variables are set by snapd rather than by the user, so they are used as they are.
+    fn env_var_path(&self, env_var: &str) -> Option<PathBuf> {
+        self.base_strategy
+            .env
+            .var_os(env_var)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+    }
+}
+
+impl<E: Environment> super::AppStrategy for Snap<E> {
+    fn home_dir(&self) -> &Path {
+        self.base_strategy.home_dir()
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.user_data_dir().join(".config").join(&self.unixy_name)
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.user_common_dir()
+            .join(".local/share")
+            .join(&self.unixy_name)
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.user_common_dir().join(".cache").join(&self.unixy_name)
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        Some(
+            self.user_common_dir()
+                .join(".local/state")
+                .join(&self.unixy_name),
+        )
+    }
+
+    // snapd points `XDG_RUNTIME_DIR` at a directory for the snap, e.g. `/run/user/1000/snap.<snap-name>`.
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.base_strategy
+            .runtime_dir()
+            .map(|runtime_dir| runtime_dir.join(&self.unixy_name))
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy.bin_dir()
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        self.base_strategy.is_bin_dir_in_path()
+    }
+}
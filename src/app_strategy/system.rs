@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+
+/// This strategy follows the [Filesystem Hierarchy Standard](https://refspecs.linuxfoundation.org/FHS_3.0/fhs/index.html), and is meant for system-wide services such as daemons rather than for the applications of a single user.
+///
+/// All of the directories are inside a prefix, which is `/` by default. A different prefix, such as `/usr/local` or `/opt/<app>`, keeps the same layout inside it.
+/// Services often run as a user that has no home directory, so unlike the other strategies, this one never fails to be created. Without a home directory, [`home_dir`](trait.AppStrategy.html#tymethod.home_dir) returns the data directory instead.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::System;
+/// use std::path::Path;
+///
+/// let app_strategy = System::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// });
+///
+/// assert_eq!(app_strategy.config_dir(), Path::new("/etc/frobnicator-plus/"));
+/// assert_eq!(app_strategy.data_dir(), Path::new("/var/lib/frobnicator-plus/"));
+/// assert_eq!(app_strategy.cache_dir(), Path::new("/var/cache/frobnicator-plus/"));
+/// assert_eq!(app_strategy.state_dir().unwrap(), Path::new("/var/lib/frobnicator-plus/"));
+/// assert_eq!(app_strategy.log_dir(), Path::new("/var/log/frobnicator-plus/"));
+/// assert_eq!(app_strategy.runtime_dir().unwrap(), Path::new("/run/frobnicator-plus/"));
+/// assert_eq!(app_strategy.bin_dir(), Path::new("/usr/bin/"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct System {
+    home_dir: PathBuf,
+    prefix: PathBuf,
+    unixy_name: String,
+}
+
+impl System {
+    /// Create a new System AppStrategy
+    pub fn new(args: super::AppStrategyArgs) -> Self {
+        Self::with_prefix(args, "/")
+    }
+
+    /// Create a new System AppStrategy whose directories are inside the given prefix instead of `/`
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::System;
+    /// use std::path::Path;
+    ///
+    /// let app_strategy = System::with_prefix(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, "/opt/frobnicator-plus");
+    ///
+    /// assert_eq!(app_strategy.config_dir(), Path::new("/opt/frobnicator-plus/etc/frobnicator-plus/"));
+    /// assert_eq!(app_strategy.log_dir(), Path::new("/opt/frobnicator-plus/var/log/frobnicator-plus/"));
+    /// assert_eq!(app_strategy.runtime_dir().unwrap(), Path::new("/opt/frobnicator-plus/run/frobnicator-plus/"));
+    /// assert_eq!(app_strategy.bin_dir(), Path::new("/opt/frobnicator-plus/bin/"));
+    /// ```
+    pub fn with_prefix(args: super::AppStrategyArgs, prefix: impl Into<PathBuf>) -> Self {
+        let prefix = prefix.into();
+        let unixy_name = args.unixy_name();
+        let home_dir =
+            crate::home_dir().unwrap_or_else(|_| prefix.join("var/lib").join(&unixy_name));
+
+        Self {
+            home_dir,
+            prefix,
+            unixy_name,
+        }
+    }
+
+    /// Gets the prefix that all of the directories are inside of.
+    pub fn prefix(&self) -> &Path {
+        &self.prefix
+    }
+
+    /// Gets your application’s log directory, e.g. `/var/log/<app>`.
+    pub fn log_dir(&self) -> PathBuf {
+        self.in_prefix("var/log")
+    }
+
+    fn in_prefix(&self, dir: &str) -> PathBuf {
+        self.prefix.join(dir).join(&self.unixy_name)
+    }
+}
+
+impl super::AppStrategy for System {
+    fn home_dir(&self) -> &Path {
+        &self.home_dir
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.in_prefix("etc")
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.in_prefix("var/lib")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.in_prefix("var/cache")
+    }
+
+    // The FHS has no separate directory for state, which it keeps in `/var/lib` as well.
+    fn state_dir(&self) -> Option<PathBuf> {
+        Some(self.data_dir())
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        Some(self.in_prefix("run"))
+    }
+
+    // `/bin` is only kept for compatibility, so the root prefix uses `/usr/bin` instead.
+    fn bin_dir(&self) -> PathBuf {
+        if self.prefix == Path::new("/") {
+            PathBuf::from("/usr/bin/")
+        } else {
+            self.prefix.join("bin/")
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+
+use super::{AppStrategy, System, Xdg};
+use crate::HomeDirError;
+use crate::base_strategy::BaseStrategy;
+use crate::environment::{Environment, ProcessEnvironment};
+
+/// This strategy uses the directories that systemd has set up for a service, and falls back to another strategy for the ones it hasn’t.
+///
+/// When a unit sets `ConfigurationDirectory=`, `StateDirectory=`, `CacheDirectory=`, `RuntimeDirectory=` or `LogsDirectory=`, systemd creates the directory and passes its path to the service in `$CONFIGURATION_DIRECTORY`, `$STATE_DIRECTORY` and so on. If a setting lists several directories, systemd separates their paths with colons, and the first one is used.
+/// systemd has no separate data directory, so `$STATE_DIRECTORY` is used for both the data and the state directory.
+/// Like with the [`Xdg`](struct.Xdg.html) strategy, paths that aren’t absolute are ignored.
+///
+/// System services fall back to the [`System`](struct.System.html) strategy, and services run with `systemctl --user` fall back to the [`Xdg`](struct.Xdg.html) strategy, which match where systemd would put these directories.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::{System, SystemdService};
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// # if cfg!(windows) { return; } // These aren’t absolute paths on Windows, where systemd doesn’t run anyway.
+/// let env = MapEnvironment::without_home_dir()
+///     .with_var("STATE_DIRECTORY", "/var/lib/frobnicator:/var/lib/frobnicator-cache")
+///     .with_var("LOGS_DIRECTORY", "/var/log/frobnicator")
+///     .with_var("CACHE_DIRECTORY", "relative/cache");
+///
+/// let app_strategy = SystemdService::with_fallback(System::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }), env);
+///
+/// // These have been set up by systemd…
+/// assert_eq!(app_strategy.data_dir(), Path::new("/var/lib/frobnicator"));
+/// assert_eq!(app_strategy.state_dir().unwrap(), Path::new("/var/lib/frobnicator"));
+/// assert_eq!(app_strategy.log_dir(), Path::new("/var/log/frobnicator"));
+///
+/// // …and these haven’t, or not with an absolute path.
+/// assert_eq!(app_strategy.config_dir(), Path::new("/etc/frobnicator-plus/"));
+/// assert_eq!(app_strategy.cache_dir(), Path::new("/var/cache/frobnicator-plus/"));
+/// assert_eq!(app_strategy.runtime_dir().unwrap(), Path::new("/run/frobnicator-plus/"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SystemdService<S = System, E = ProcessEnvironment> {
+    fallback: S,
+    env: E,
+}
+
+impl SystemdService {
+    /// Create a new SystemdService AppStrategy for a system service
+    pub fn new(args: super::AppStrategyArgs) -> Self {
+        Self::with_fallback(System::new(args), ProcessEnvironment)
+    }
+}
+
+impl SystemdService<Xdg> {
+    /// Create a new SystemdService AppStrategy for a user service
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::{SystemdService, Xdg};
+    /// use etcetera::environment::MapEnvironment;
+    /// use std::path::Path;
+    ///
+    /// # if cfg!(windows) { return; } // These aren’t absolute paths on Windows, where systemd doesn’t run anyway.
+    /// let env = MapEnvironment::new("/home/frobnicator")
+    ///     .with_var("CACHE_DIRECTORY", "/home/frobnicator/.cache/frobnicator");
+    ///
+    /// let app_strategy = SystemdService::with_fallback(Xdg::with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, env.clone()).unwrap(), env);
+    ///
+    /// assert_eq!(app_strategy.cache_dir(), Path::new("/home/frobnicator/.cache/frobnicator"));
+    /// assert_eq!(
+    ///     app_strategy.log_dir(),
+    ///     Path::new("/home/frobnicator/.local/state/log/frobnicator-plus")
+    /// );
+    /// ```
+    pub fn user(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+        Ok(Self::with_fallback(Xdg::new(args)?, ProcessEnvironment))
+    }
+}
+
+impl<S: AppStrategy, E: Environment> SystemdService<S, E> {
+    /// Create a new SystemdService AppStrategy that reads from the given environment, and falls back to the given strategy
+    pub fn with_fallback(fallback: S, env: E) -> Self {
+        Self { fallback, env }
+    }
+
+    /// Gets the strategy that is used for the directories that systemd hasn’t set up.
+    pub fn fallback(&self) -> &S {
+        &self.fallback
+    }
+
+    fn service_dir(&self, env_var: &str) -> Option<PathBuf> {
+        let paths = self.env.var_os(env_var)?;
+        // A path that isn’t valid Unicode can’t be split, so it is used as it is.
+        let path = match paths.to_str() {
+            Some(paths) => paths.split(':').next().unwrap_or_default().into(),
+            None => paths,
+        };
+        Some(PathBuf::from(path)).filter(|path| path.is_absolute())
+    }
+}
+
+impl<E: Environment> SystemdService<System, E> {
+    /// Gets your application’s log directory, e.g. `/var/log/<app>`.
+    pub fn log_dir(&self) -> PathBuf {
+        self.service_dir("LOGS_DIRECTORY")
+            .unwrap_or_else(|| self.fallback.log_dir())
+    }
+}
+
+impl<E: Environment, F: Environment> SystemdService<Xdg<F>, E> {
+    /// Gets your application’s log directory, e.g. `~/.local/state/log/<app>`.
+    pub fn log_dir(&self) -> PathBuf {
+        self.service_dir("LOGS_DIRECTORY").unwrap_or_else(|| {
+            // For user services, systemd keeps logs in a directory of their own inside the state directory.
+            self.fallback
+                .base_strategy()
+                .state_dir()
+                .unwrap_or_else(|| self.fallback.home_dir().join(".local/state/"))
+                .join("log")
+                .join(self.fallback.unixy_name())
+        })
+    }
+}
+
+impl<S: AppStrategy, E: Environment> AppStrategy for SystemdService<S, E> {
+    fn home_dir(&self) -> &Path {
+        self.fallback.home_dir()
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.service_dir("CONFIGURATION_DIRECTORY")
+            .unwrap_or_else(|| self.fallback.config_dir())
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.service_dir("STATE_DIRECTORY")
+            .unwrap_or_else(|| self.fallback.data_dir())
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.service_dir("CACHE_DIRECTORY")
+            .unwrap_or_else(|| self.fallback.cache_dir())
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.service_dir("STATE_DIRECTORY")
+            .or_else(|| self.fallback.state_dir())
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.service_dir("RUNTIME_DIRECTORY")
+            .or_else(|| self.fallback.runtime_dir())
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.fallback.bin_dir()
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.env)
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.fallback.config_dirs()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.fallback.data_dirs()
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use crate::HomeDirError;
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
 
 /// This strategy has no standard or official specification. It has arisen over time through hundreds of Unixy tools. Vim and Cargo are notable examples whose configuration/data/cache directory layouts are similar to those created by this strategy.
 ///
@@ -42,25 +43,70 @@ use crate::HomeDirError;
 ///     app_strategy.runtime_dir().unwrap().strip_prefix(&home_dir),
 ///     Ok(Path::new(".frobnicator-plus/runtime/"))
 /// );
+/// assert_eq!(
+///     app_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".frobnicator-plus/bin/"))
+/// );
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Unix {
-    // This is `.frobnicator-plus` in the above example.
+pub struct Unix<E = ProcessEnvironment> {
     home_dir: PathBuf,
+    // This is `.frobnicator-plus` in the above example.
     unixy_name: String,
+    env: E,
 }
 
 impl Unix {
     /// Create a new Unix AppStrategy
     pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
+        Self::with_env(args, ProcessEnvironment)
+    }
+}
+
+impl Unix<MapEnvironment> {
+    /// Create a new Unix AppStrategy for the given home directory instead of the current user’s one
+    ///
+    /// The environment of the current process isn’t read.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Unix;
+    /// use std::path::Path;
+    ///
+    /// let app_strategy = Unix::with_home(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, "/home/frobnicator");
+    ///
+    /// assert_eq!(app_strategy.config_dir(), Path::new("/home/frobnicator/.frobnicator-plus"));
+    /// assert_eq!(app_strategy.data_dir(), Path::new("/home/frobnicator/.frobnicator-plus/data/"));
+    /// ```
+    pub fn with_home(args: super::AppStrategyArgs, home_dir: impl Into<PathBuf>) -> Self {
+        let home_dir = home_dir.into();
+        Self {
+            env: MapEnvironment::new(&home_dir),
+            home_dir,
+            unixy_name: format!(".{}", args.unixy_name()),
+        }
+    }
+}
+
+impl<E: Environment> Unix<E> {
+    /// Create a new Unix AppStrategy that reads from the given environment instead of the current process’s one
+    ///
+    /// The directories only depend on the environment’s home directory, but its `PATH` is used by [`is_bin_dir_in_path`](trait.AppStrategy.html#method.is_bin_dir_in_path).
+    pub fn with_env(args: super::AppStrategyArgs, env: E) -> Result<Self, HomeDirError> {
         Ok(Self {
-            home_dir: crate::home_dir()?,
+            home_dir: env.home_dir().ok_or(HomeDirError)?,
             unixy_name: format!(".{}", args.unixy_name()),
+            env,
         })
     }
 }
 
-impl super::AppStrategy for Unix {
+impl<E: Environment> super::AppStrategy for Unix<E> {
     fn home_dir(&self) -> &Path {
         &self.home_dir
     }
@@ -84,4 +130,12 @@ impl super::AppStrategy for Unix {
     fn runtime_dir(&self) -> Option<PathBuf> {
         Some(self.home_dir.join(&self.unixy_name).join("runtime/"))
     }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.home_dir.join(&self.unixy_name).join("bin/")
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.env)
+    }
 }
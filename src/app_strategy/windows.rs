@@ -1,4 +1,5 @@
 use crate::base_strategy::BaseStrategy;
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
 use crate::{HomeDirError, base_strategy};
 use std::path::{Path, PathBuf};
 
@@ -53,6 +54,10 @@ use std::path::{Path, PathBuf};
 ///     app_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     app_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Programs/Acme Corp/Frobnicator Plus"))
+/// );
 /// ```
 ///
 /// This next example gives the environment variables values:
@@ -115,12 +120,18 @@ use std::path::{Path, PathBuf};
 ///     app_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     app_strategy.bin_dir(),
+///     Path::new(cache_path).join("Programs/Acme Corp/Frobnicator Plus")
+/// );
 /// ```
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Windows {
-    base_strategy: base_strategy::Windows,
+pub struct Windows<E = ProcessEnvironment> {
+    base_strategy: base_strategy::Windows<E>,
     author_app_name_path: PathBuf,
+    author: String,
+    app_name: String,
 }
 
 macro_rules! dir_method {
@@ -136,14 +147,53 @@ macro_rules! dir_method {
 impl Windows {
     /// Create a new Windows AppStrategy
     pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
-        Ok(Self {
-            base_strategy: base_strategy::Windows::new()?,
-            author_app_name_path: PathBuf::from(args.author).join(args.app_name),
-        })
+        Self::with_env(args, ProcessEnvironment)
+    }
+}
+
+impl Windows<MapEnvironment> {
+    /// Create a new Windows AppStrategy for the given home directory instead of the current user’s one
+    ///
+    /// See [`base_strategy::Windows::with_home`] for how the known folders are treated.
+    pub fn with_home(args: super::AppStrategyArgs, home_dir: impl Into<PathBuf>) -> Self {
+        Self::from_base_strategy(args, base_strategy::Windows::with_home(home_dir))
+    }
+}
+
+impl<E: Environment> Windows<E> {
+    /// Create a new Windows AppStrategy that reads from the given environment instead of the current process’s one
+    pub fn with_env(args: super::AppStrategyArgs, env: E) -> Result<Self, HomeDirError> {
+        Ok(Self::from_base_strategy(
+            args,
+            base_strategy::Windows::with_env(env)?,
+        ))
+    }
+
+    fn from_base_strategy(
+        args: super::AppStrategyArgs, base_strategy: base_strategy::Windows<E>,
+    ) -> Self {
+        Self {
+            base_strategy,
+            author_app_name_path: PathBuf::from(&args.author).join(&args.app_name),
+            author: args.author,
+            app_name: args.app_name,
+        }
+    }
+
+    pub(crate) fn base_strategy(&self) -> &base_strategy::Windows<E> {
+        &self.base_strategy
+    }
+
+    pub(crate) fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub(crate) fn app_name(&self) -> &str {
+        &self.app_name
     }
 }
 
-impl super::AppStrategy for Windows {
+impl<E: Environment> super::AppStrategy for Windows<E> {
     fn home_dir(&self) -> &Path {
         self.base_strategy.home_dir()
     }
@@ -167,4 +217,14 @@ impl super::AppStrategy for Windows {
     fn runtime_dir(&self) -> Option<PathBuf> {
         None
     }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy
+            .bin_dir()
+            .join(&self.author_app_name_path)
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.base_strategy.env)
+    }
 }
@@ -0,0 +1,110 @@
+use crate::base_strategy;
+use crate::base_strategy::BaseStrategy;
+use crate::environment::{Environment, ProcessEnvironment};
+use std::path::{Path, PathBuf};
+
+/// This is the machine-wide counterpart to the [`Windows`](struct.Windows.html) strategy, for files that are shared between all users of a computer, e.g. by a service. It names your application’s directories in the same way, but keeps them in [`ProgramData`](../base_strategy/struct.WindowsMachine.html) instead of the user’s `AppData`.
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::WindowsMachine;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// let app_strategy = WindowsMachine::with_env(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }, MapEnvironment::new("C:\\Users\\frobnicator"));
+///
+/// if !cfg!(windows) {
+///     let program_data = Path::new("C:\\ProgramData");
+///
+///     assert_eq!(
+///         app_strategy.config_dir(),
+///         program_data.join("Acme Corp/Frobnicator Plus/config")
+///     );
+///     assert_eq!(
+///         app_strategy.data_dir(),
+///         program_data.join("Acme Corp/Frobnicator Plus/data")
+///     );
+///     assert_eq!(
+///         app_strategy.cache_dir(),
+///         program_data.join("Acme Corp/Frobnicator Plus/cache")
+///     );
+///     assert_eq!(
+///         app_strategy.bin_dir(),
+///         Path::new("C:\\Program Files").join("Acme Corp/Frobnicator Plus")
+///     );
+/// }
+/// assert_eq!(app_strategy.state_dir(), None);
+/// assert_eq!(app_strategy.runtime_dir(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowsMachine<E = ProcessEnvironment> {
+    base_strategy: base_strategy::WindowsMachine<E>,
+    author_app_name_path: PathBuf,
+}
+
+impl WindowsMachine {
+    /// Create a new WindowsMachine AppStrategy
+    pub fn new(args: super::AppStrategyArgs) -> Self {
+        Self::with_env(args, ProcessEnvironment)
+    }
+}
+
+impl<E: Environment> WindowsMachine<E> {
+    /// Create a new WindowsMachine AppStrategy that reads from the given environment instead of the current process’s one
+    pub fn with_env(args: super::AppStrategyArgs, env: E) -> Self {
+        Self {
+            base_strategy: base_strategy::WindowsMachine::with_env(env),
+            author_app_name_path: PathBuf::from(args.author).join(args.app_name),
+        }
+    }
+}
+
+impl<E: Environment> super::AppStrategy for WindowsMachine<E> {
+    fn home_dir(&self) -> &Path {
+        self.base_strategy.home_dir()
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.base_strategy
+            .config_dir()
+            .join(&self.author_app_name_path)
+            .join("config")
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.base_strategy
+            .data_dir()
+            .join(&self.author_app_name_path)
+            .join("data")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.base_strategy
+            .cache_dir()
+            .join(&self.author_app_name_path)
+            .join("cache")
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy
+            .bin_dir()
+            .join(&self.author_app_name_path)
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        self.base_strategy.is_bin_dir_in_path()
+    }
+}
@@ -1,4 +1,6 @@
 use crate::base_strategy::BaseStrategy;
+use crate::desktop_entry::DesktopEntry;
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
 use crate::{HomeDirError, base_strategy};
 use std::path::{Path, PathBuf};
 
@@ -173,23 +175,242 @@ use std::path::{Path, PathBuf};
 ///     None
 /// );
 /// ```
+///
+/// Instead of the environment of the current process, the strategy can read from an [`Environment`](../environment/trait.Environment.html) of your choice:
+///
+/// ```
+/// use etcetera::app_strategy::AppStrategy;
+/// use etcetera::app_strategy::AppStrategyArgs;
+/// use etcetera::app_strategy::Xdg;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// // We need to conditionally set this to ensure that it is an absolute path both on Windows and other systems.
+/// let config_path = if cfg!(windows) {
+///     "C:\\config\\"
+/// } else {
+///     "/config/"
+/// };
+///
+/// let env = MapEnvironment::new("/home/frobnicator").with_var("XDG_CONFIG_HOME", config_path);
+///
+/// let app_strategy = Xdg::with_env(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }, env).unwrap();
+///
+/// assert_eq!(
+///     app_strategy.config_dir(),
+///     Path::new(config_path).join("frobnicator-plus")
+/// );
+/// assert_eq!(
+///     app_strategy.data_dir(),
+///     Path::new("/home/frobnicator/.local/share/frobnicator-plus")
+/// );
+/// assert_eq!(app_strategy.bin_dir(), Path::new("/home/frobnicator/.local/bin/"));
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Xdg {
-    base_strategy: base_strategy::Xdg,
+pub struct Xdg<E = ProcessEnvironment> {
+    base_strategy: base_strategy::Xdg<E>,
     unixy_name: String,
+    bundle_id: String,
+    app_name: String,
 }
 
 impl Xdg {
     /// Create a new Xdg AppStrategy
     pub fn new(args: super::AppStrategyArgs) -> Result<Self, HomeDirError> {
-        Ok(Self {
-            base_strategy: base_strategy::Xdg::new()?,
+        Self::with_env(args, ProcessEnvironment)
+    }
+}
+
+impl Xdg<MapEnvironment> {
+    /// Create a new Xdg AppStrategy for the given home directory instead of the current user’s one
+    ///
+    /// See [`base_strategy::Xdg::with_home`] for how the environment variables are treated.
+    pub fn with_home(args: super::AppStrategyArgs, home_dir: impl Into<PathBuf>) -> Self {
+        Self::from_base_strategy(args, base_strategy::Xdg::with_home(home_dir))
+    }
+}
+
+impl<E: Environment> Xdg<E> {
+    /// Create a new Xdg AppStrategy that reads from the given environment instead of the current process’s one
+    pub fn with_env(args: super::AppStrategyArgs, env: E) -> Result<Self, HomeDirError> {
+        Ok(Self::from_base_strategy(
+            args,
+            base_strategy::Xdg::with_env(env)?,
+        ))
+    }
+
+    fn from_base_strategy(
+        args: super::AppStrategyArgs, base_strategy: base_strategy::Xdg<E>,
+    ) -> Self {
+        Self {
+            base_strategy,
             unixy_name: args.unixy_name(),
-        })
+            bundle_id: args.bundle_id(),
+            app_name: args.app_name,
+        }
+    }
+
+    pub(crate) fn base_strategy(&self) -> &base_strategy::Xdg<E> {
+        &self.base_strategy
+    }
+
+    pub(crate) fn unixy_name(&self) -> &str {
+        &self.unixy_name
+    }
+
+    pub(crate) fn bundle_id(&self) -> &str {
+        &self.bundle_id
+    }
+
+    pub(crate) fn app_name(&self) -> &str {
+        &self.app_name
+    }
+
+    /// Gets the path of your application’s desktop entry, which makes it show up in application launchers.
+    ///
+    /// The desktop entry is named after your application’s [bundle identifier](struct.AppStrategyArgs.html#method.bundle_id), and lives in the `applications` directory inside the user’s data directory.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use std::path::Path;
+    ///
+    /// let home_dir = Path::new("/home/frobnicator");
+    ///
+    /// let app_strategy = Xdg::with_home(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, home_dir);
+    ///
+    /// assert_eq!(
+    ///     app_strategy.desktop_entry_path().strip_prefix(&home_dir),
+    ///     Ok(Path::new(".local/share/applications/org.acme-corp.Frobnicator-Plus.desktop"))
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.icon_path(48).strip_prefix(&home_dir),
+    ///     Ok(Path::new(".local/share/icons/hicolor/48x48/apps/org.acme-corp.Frobnicator-Plus.png"))
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.scalable_icon_path().strip_prefix(&home_dir),
+    ///     Ok(Path::new(".local/share/icons/hicolor/scalable/apps/org.acme-corp.Frobnicator-Plus.svg"))
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.mime_package_path().strip_prefix(&home_dir),
+    ///     Ok(Path::new(".local/share/mime/packages/org.acme-corp.Frobnicator-Plus.xml"))
+    /// );
+    /// ```
+    pub fn desktop_entry_path(&self) -> PathBuf {
+        self.base_strategy
+            .data_dir()
+            .join("applications")
+            .join(format!("{}.desktop", self.bundle_id))
+    }
+
+    /// Gets the path of your application’s PNG icon of the given size, in pixels, in the `hicolor` icon theme.
+    ///
+    /// The icon is named after your application’s [bundle identifier](struct.AppStrategyArgs.html#method.bundle_id), so that a desktop entry can refer to it by that name.
+    pub fn icon_path(&self, size: u32) -> PathBuf {
+        self.icon_dir(&format!("{size}x{size}"))
+            .join(format!("{}.png", self.bundle_id))
+    }
+
+    /// Gets the path of your application’s SVG icon in the `hicolor` icon theme.
+    ///
+    /// The icon is named after your application’s [bundle identifier](struct.AppStrategyArgs.html#method.bundle_id), so that a desktop entry can refer to it by that name.
+    pub fn scalable_icon_path(&self) -> PathBuf {
+        self.icon_dir("scalable")
+            .join(format!("{}.svg", self.bundle_id))
+    }
+
+    fn icon_dir(&self, size: &str) -> PathBuf {
+        self.base_strategy
+            .data_dir()
+            .join("icons/hicolor")
+            .join(size)
+            .join("apps")
+    }
+
+    /// Gets the path of the [shared MIME-info](https://specifications.freedesktop.org/shared-mime-info-spec/latest/) package in which your application defines its file types.
+    ///
+    /// Note: After changing the package, `update-mime-database` has to be run on the `mime` directory that contains it.
+    pub fn mime_package_path(&self) -> PathBuf {
+        self.base_strategy
+            .data_dir()
+            .join("mime/packages")
+            .join(format!("{}.xml", self.bundle_id))
+    }
+
+    /// Writes your application’s desktop entry to the [path where it belongs](#method.desktop_entry_path), creating the directory it lives in if necessary.
+    ///
+    /// An entry that doesn’t pass [`DesktopEntry::validate`] isn’t written, and results in an error of the kind [`InvalidInput`](std::io::ErrorKind::InvalidInput).
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use etcetera::desktop_entry::DesktopEntry;
+    /// use etcetera::environment::MapEnvironment;
+    ///
+    /// let data_home = std::env::temp_dir().join("etcetera-write-desktop-entry");
+    ///
+    /// let env = MapEnvironment::new(std::env::temp_dir()).with_var("XDG_DATA_HOME", &data_home);
+    ///
+    /// let app_strategy = Xdg::with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, env).unwrap();
+    ///
+    /// let entry = DesktopEntry::new("Frobnicator Plus", ["frobnicator-plus"]);
+    /// app_strategy.write_desktop_entry(&entry).unwrap();
+    ///
+    /// assert_eq!(
+    ///     std::fs::read_to_string(app_strategy.desktop_entry_path()).unwrap(),
+    ///     entry.to_string()
+    /// );
+    ///
+    /// std::fs::remove_dir_all(data_home).unwrap();
+    /// ```
+    pub fn write_desktop_entry(&self, entry: &DesktopEntry) -> std::io::Result<()> {
+        entry
+            .validate()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        let path = self.desktop_entry_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, entry.to_string())
+    }
+
+    /// Gets the runtime directory for your application, after checking that the user’s runtime directory meets the requirements of the [XDG Base Directory Specification](https://specifications.freedesktop.org/basedir/latest/).
+    ///
+    /// See [`base_strategy::Xdg::checked_runtime_dir`] for the checks that are performed.
+    /// The application’s subdirectory itself isn’t checked, as it may not have been created yet.
+    #[cfg(unix)]
+    pub fn checked_runtime_dir(&self) -> Result<PathBuf, base_strategy::RuntimeDirError> {
+        self.base_strategy
+            .checked_runtime_dir()
+            .map(|runtime_dir| runtime_dir.join(&self.unixy_name))
+    }
+
+    /// Gets the runtime directory for your application like [`checked_runtime_dir`](#method.checked_runtime_dir), but falls back to a replacement directory when `XDG_RUNTIME_DIR` isn’t set.
+    ///
+    /// See [`base_strategy::Xdg::runtime_dir_or_fallback`] for how the replacement directory is chosen.
+    #[cfg(unix)]
+    pub fn runtime_dir_or_fallback(
+        &self,
+    ) -> Result<base_strategy::RuntimeDir, base_strategy::RuntimeDirError> {
+        self.base_strategy
+            .runtime_dir_or_fallback()
+            .map(|runtime_dir| runtime_dir.join(&self.unixy_name))
     }
 }
 
-impl super::AppStrategy for Xdg {
+impl<E: Environment> super::AppStrategy for Xdg<E> {
     fn home_dir(&self) -> &Path {
         self.base_strategy.home_dir()
     }
@@ -220,4 +441,73 @@ impl super::AppStrategy for Xdg {
             .runtime_dir()
             .map(|runtime_dir| runtime_dir.join(&self.unixy_name))
     }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.base_strategy.bin_dir()
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        self.base_strategy.is_bin_dir_in_path()
+    }
+
+    /// Gets the system-wide configuration directories for your application, in order of decreasing precedence.
+    ///
+    /// See [`base_strategy::Xdg::config_dirs`] for how these are determined.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use std::path::PathBuf;
+    ///
+    /// let app_strategy = Xdg::with_home(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, "/home/frobnicator");
+    ///
+    /// assert_eq!(
+    ///     app_strategy.config_dirs(),
+    ///     [PathBuf::from("/etc/xdg/frobnicator-plus/")]
+    /// );
+    /// ```
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.base_strategy
+            .config_dirs()
+            .into_iter()
+            .map(|config_dir| config_dir.join(&self.unixy_name))
+            .collect()
+    }
+
+    /// Gets the system-wide data directories for your application, in order of decreasing precedence.
+    ///
+    /// See [`base_strategy::Xdg::data_dirs`] for how these are determined.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategy;
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use std::path::PathBuf;
+    ///
+    /// let app_strategy = Xdg::with_home(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, "/home/frobnicator");
+    ///
+    /// assert_eq!(
+    ///     app_strategy.data_dirs(),
+    ///     [
+    ///         PathBuf::from("/usr/local/share/frobnicator-plus/"),
+    ///         PathBuf::from("/usr/share/frobnicator-plus/"),
+    ///     ]
+    /// );
+    /// ```
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.base_strategy
+            .data_dirs()
+            .into_iter()
+            .map(|data_dir| data_dir.join(&self.unixy_name))
+            .collect()
+    }
 }
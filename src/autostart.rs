@@ -0,0 +1,292 @@
+//! These register your application to be launched when the user logs in.
+
+use std::path::PathBuf;
+
+use crate::app_strategy::{self, AppStrategy};
+use crate::base_strategy::BaseStrategy;
+use crate::desktop_entry::DesktopEntry;
+use crate::environment::Environment;
+
+/// Registers your application to be launched when the user logs in.
+///
+/// - The [`Xdg`](../app_strategy/struct.Xdg.html) strategy uses an [autostart](https://specifications.freedesktop.org/autostart-spec/latest/) desktop entry in the `autostart` directory inside the user’s configuration directory.
+/// - The [`Apple`](../app_strategy/struct.Apple.html) strategy uses a launch agent in `~/Library/LaunchAgents`.
+/// - The [`Windows`](../app_strategy/struct.Windows.html) strategy uses a batch file, named after the author and the application, in the user’s `Startup` folder.
+///
+/// The files are generated the same way on every platform, so they can be inspected anywhere:
+///
+/// ```
+/// use etcetera::app_strategy::{AppStrategy, AppStrategyArgs, Apple};
+/// use etcetera::autostart::Autostart;
+/// use std::path::Path;
+///
+/// let app_strategy = Apple::new(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }).unwrap();
+///
+/// assert_eq!(
+///     app_strategy.autostart_path().strip_prefix(app_strategy.home_dir()),
+///     Ok(Path::new("Library/LaunchAgents/org.acme-corp.Frobnicator-Plus.plist"))
+/// );
+/// assert_eq!(
+///     app_strategy.autostart_contents(&["/Applications/Frobnicator Plus.app/Contents/MacOS/frob", "--tray"]),
+///     r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+/// <plist version="1.0">
+/// <dict>
+///     <key>Label</key>
+///     <string>org.acme-corp.Frobnicator-Plus</string>
+///     <key>ProgramArguments</key>
+///     <array>
+///         <string>/Applications/Frobnicator Plus.app/Contents/MacOS/frob</string>
+///         <string>--tray</string>
+///     </array>
+///     <key>RunAtLoad</key>
+///     <true/>
+/// </dict>
+/// </plist>
+/// "#
+/// );
+/// ```
+///
+/// Installing and removing the registration can be repeated safely:
+///
+/// ```
+/// use etcetera::app_strategy::{AppStrategyArgs, Xdg};
+/// use etcetera::autostart::Autostart;
+/// use etcetera::environment::MapEnvironment;
+///
+/// let config_home = std::env::temp_dir().join("etcetera-autostart");
+///
+/// let env = MapEnvironment::new(std::env::temp_dir()).with_var("XDG_CONFIG_HOME", &config_home);
+///
+/// let app_strategy = Xdg::with_env(AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// }, env).unwrap();
+///
+/// assert_eq!(
+///     app_strategy.autostart_path(),
+///     config_home.join("autostart/org.acme-corp.Frobnicator-Plus.desktop")
+/// );
+///
+/// app_strategy.install_autostart(&["frobnicator-plus", "--tray"]).unwrap();
+/// app_strategy.install_autostart(&["frobnicator-plus", "--tray"]).unwrap();
+/// assert!(app_strategy.is_autostart_installed());
+///
+/// app_strategy.remove_autostart().unwrap();
+/// app_strategy.remove_autostart().unwrap();
+/// assert!(!app_strategy.is_autostart_installed());
+///
+/// std::fs::remove_dir_all(config_home).unwrap();
+/// ```
+pub trait Autostart: AppStrategy {
+    /// Gets the path of the file that registers your application to be launched when the user logs in.
+    fn autostart_path(&self) -> PathBuf;
+
+    /// Generates the contents of the file that registers your application to be launched, with the given program and arguments, when the user logs in.
+    fn autostart_contents<S: AsRef<str>>(&self, command: &[S]) -> String;
+
+    /// Registers your application to be launched, with the given program and arguments, when the user logs in.
+    ///
+    /// Nothing is written if the registration is already up to date.
+    fn install_autostart<S: AsRef<str>>(&self, command: &[S]) -> std::io::Result<()> {
+        let path = self.autostart_path();
+        let contents = self.autostart_contents(command);
+
+        if std::fs::read_to_string(&path).is_ok_and(|existing| existing == contents) {
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)
+    }
+
+    /// Stops your application from being launched when the user logs in.
+    ///
+    /// It isn’t an error if your application wasn’t registered.
+    fn remove_autostart(&self) -> std::io::Result<()> {
+        match std::fs::remove_file(self.autostart_path()) {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Returns whether your application is registered to be launched when the user logs in.
+    fn is_autostart_installed(&self) -> bool {
+        self.autostart_path().is_file()
+    }
+}
+
+impl<E: Environment> Autostart for app_strategy::Xdg<E> {
+    fn autostart_path(&self) -> PathBuf {
+        self.base_strategy()
+            .config_dir()
+            .join("autostart")
+            .join(format!("{}.desktop", self.bundle_id()))
+    }
+
+    fn autostart_contents<S: AsRef<str>>(&self, command: &[S]) -> String {
+        let mut entry = DesktopEntry::new(
+            self.app_name(),
+            command.iter().map(|arg| arg.as_ref().to_string()),
+        );
+        entry
+            .extra_keys
+            .push(("X-GNOME-Autostart-enabled".to_string(), "true".to_string()));
+        entry.to_string()
+    }
+}
+
+impl<E: Environment> Autostart for app_strategy::Apple<E> {
+    fn autostart_path(&self) -> PathBuf {
+        self.home_dir()
+            .join("Library/LaunchAgents")
+            .join(format!("{}.plist", self.bundle_id()))
+    }
+
+    fn autostart_contents<S: AsRef<str>>(&self, command: &[S]) -> String {
+        let mut contents = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+            "<plist version=\"1.0\">\n",
+            "<dict>\n",
+            "    <key>Label</key>\n",
+        ));
+        contents.push_str(&format!(
+            "    <string>{}</string>\n",
+            escape_xml(self.bundle_id())
+        ));
+        contents.push_str("    <key>ProgramArguments</key>\n");
+        contents.push_str("    <array>\n");
+        for arg in command {
+            contents.push_str(&format!(
+                "        <string>{}</string>\n",
+                escape_xml(arg.as_ref())
+            ));
+        }
+        contents.push_str(concat!(
+            "    </array>\n",
+            "    <key>RunAtLoad</key>\n",
+            "    <true/>\n",
+            "</dict>\n",
+            "</plist>\n",
+        ));
+        contents
+    }
+}
+
+impl<E: Environment> Autostart for app_strategy::Windows<E> {
+    /// ```
+    /// use etcetera::app_strategy::{AppStrategyArgs, Windows};
+    /// use etcetera::autostart::Autostart;
+    /// use etcetera::environment::MapEnvironment;
+    /// use std::path::Path;
+    ///
+    /// // We need to conditionally set this to ensure that it is an absolute path both on Windows and other systems.
+    /// let data_path = if cfg!(windows) {
+    ///     "C:\\Users\\frobnicator\\AppData\\Roaming\\"
+    /// } else {
+    ///     "/home/frobnicator/AppData/Roaming/"
+    /// };
+    ///
+    /// let env = MapEnvironment::new(data_path).with_var("APPDATA", data_path);
+    ///
+    /// let app_strategy = Windows::with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator: Plus".to_string(),
+    /// }, env).unwrap();
+    ///
+    /// // The file is named after the author as well, so that it doesn’t clash with another author’s application of the same name.
+    /// // Characters that can’t appear in file names are replaced.
+    /// assert_eq!(
+    ///     app_strategy.autostart_path().strip_prefix(data_path),
+    ///     Ok(Path::new("Microsoft/Windows/Start Menu/Programs/Startup/Acme Corp Frobnicator_ Plus.cmd"))
+    /// );
+    /// assert_eq!(
+    ///     app_strategy.autostart_contents(&[r"C:\Program Files\Frobnicator Plus\frob.exe", "--discount=100%"]),
+    ///     "@echo off\r\nstart \"\" \"C:\\Program Files\\Frobnicator Plus\\frob.exe\" \"--discount=100%%\"\r\n"
+    /// );
+    ///
+    /// // Quotes are doubled, as are backslashes right before a quote, and line breaks are left out.
+    /// assert_eq!(
+    ///     app_strategy.autostart_contents(&["frob.exe", "say \"hi\"\n& exit", r"C:\Frobnications\"]),
+    ///     "@echo off\r\nstart \"\" \"frob.exe\" \"say \"\"hi\"\"& exit\" \"C:\\Frobnications\\\\\"\r\n"
+    /// );
+    /// ```
+    fn autostart_path(&self) -> PathBuf {
+        self.base_strategy()
+            .config_dir()
+            .join("Microsoft/Windows/Start Menu/Programs/Startup")
+            .join(format!(
+                "{}.cmd",
+                sanitize_file_name(&format!("{} {}", self.author(), self.app_name()))
+            ))
+    }
+
+    fn autostart_contents<S: AsRef<str>>(&self, command: &[S]) -> String {
+        // The empty title stops `start` from mistaking a quoted program for the window’s title.
+        let mut contents = String::from("@echo off\r\nstart \"\"");
+        for arg in command {
+            contents.push_str(" \"");
+            let mut backslashes = 0;
+            for c in arg.as_ref().chars() {
+                if c == '\\' {
+                    backslashes += 1;
+                    continue;
+                }
+                // Backslashes are only special right before a quote, where they have to be doubled.
+                let escaped_backslashes = if c == '"' {
+                    backslashes * 2
+                } else {
+                    backslashes
+                };
+                contents.push_str(&"\\".repeat(escaped_backslashes));
+                backslashes = 0;
+
+                match c {
+                    // Doubled quotes stay inside the quoted argument, both for `cmd.exe` and for the program.
+                    '"' => contents.push_str("\"\""),
+                    // Batch files would otherwise expand variables.
+                    '%' => contents.push_str("%%"),
+                    // Line breaks would end the command, so control characters are left out.
+                    c if c.is_control() => {}
+                    c => contents.push(c),
+                }
+            }
+            // The closing quote follows the remaining backslashes.
+            contents.push_str(&"\\".repeat(backslashes * 2));
+            contents.push('"');
+        }
+        contents.push_str("\r\n");
+        contents
+    }
+}
+
+// Replaces the characters that Windows doesn’t allow in file names.
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_control() || "<>:\"/\\|?*".contains(c) {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
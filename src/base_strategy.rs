@@ -1,9 +1,12 @@
 //! These strategies simply provide the user’s configuration, data, and cache directories, without knowing about the application specifically.
 
 use crate::HomeDirError;
+use crate::environment::ProcessEnvironment;
 use std::path::{Path, PathBuf};
 
 /// Provides configuration, data, and cache directories of the current user.
+///
+/// The strategy can be stored as a trait object, such as a `Box<dyn BaseStrategy>` or an `Arc<dyn BaseStrategy>`, both of which implement the trait too.
 pub trait BaseStrategy {
     /// Gets the home directory of the current user.
     fn home_dir(&self) -> &Path;
@@ -25,13 +28,104 @@ pub trait BaseStrategy {
     /// Currently, only the [`Xdg`](struct.Xdg.html) strategy supports this.
     ///
     /// Note: The [XDG Base Directory Specification](spec) places additional requirements on this
-    /// directory related to ownership, permissions, and persistence. This library does not check
-    /// these requirements.
+    /// directory related to ownership, permissions, and persistence. This method does not check
+    /// these requirements; on Unix, [`Xdg::checked_runtime_dir`](struct.Xdg.html#method.checked_runtime_dir)
+    /// checks the ones that can be.
     ///
     /// [spec]: https://specifications.freedesktop.org/basedir/latest/
     fn runtime_dir(&self) -> Option<PathBuf>;
+
+    /// Gets the user’s directory for executables.
+    ///
+    /// This defaults to `~/.local/bin/`, which is where the XDG Base Directory Specification puts executables.
+    fn bin_dir(&self) -> PathBuf {
+        self.home_dir().join(".local/bin/")
+    }
+
+    /// Returns whether the user’s directory for executables is listed in the `PATH` environment variable.
+    ///
+    /// If it isn’t, executables that you install there can’t be run by name.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    ///
+    /// let base_strategy = Xdg::with_home("/home/frobnicator");
+    /// assert!(!base_strategy.is_bin_dir_in_path());
+    ///
+    /// let env = MapEnvironment::new("/home/frobnicator")
+    ///     .with_var("PATH", std::env::join_paths([base_strategy.bin_dir()]).unwrap());
+    /// let base_strategy = Xdg::with_env(env).unwrap();
+    /// assert!(base_strategy.is_bin_dir_in_path());
+    /// ```
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &ProcessEnvironment)
+    }
+
+    /// Gets the system-wide configuration directories, in order of decreasing precedence.
+    /// Currently, only the [`Xdg`](struct.Xdg.html) strategy supports this; all other strategies return an empty list.
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Gets the system-wide data directories, in order of decreasing precedence.
+    /// Currently, only the [`Xdg`](struct.Xdg.html) strategy supports this; all other strategies return an empty list.
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
 
+macro_rules! impl_base_strategy_for_pointer {
+    ($($pointer: ty),*) => {
+        $(
+            impl<T: BaseStrategy + ?Sized> BaseStrategy for $pointer {
+                fn home_dir(&self) -> &Path {
+                    (**self).home_dir()
+                }
+
+                fn config_dir(&self) -> PathBuf {
+                    (**self).config_dir()
+                }
+
+                fn data_dir(&self) -> PathBuf {
+                    (**self).data_dir()
+                }
+
+                fn cache_dir(&self) -> PathBuf {
+                    (**self).cache_dir()
+                }
+
+                fn state_dir(&self) -> Option<PathBuf> {
+                    (**self).state_dir()
+                }
+
+                fn runtime_dir(&self) -> Option<PathBuf> {
+                    (**self).runtime_dir()
+                }
+
+                fn bin_dir(&self) -> PathBuf {
+                    (**self).bin_dir()
+                }
+
+                fn is_bin_dir_in_path(&self) -> bool {
+                    (**self).is_bin_dir_in_path()
+                }
+
+                fn config_dirs(&self) -> Vec<PathBuf> {
+                    (**self).config_dirs()
+                }
+
+                fn data_dirs(&self) -> Vec<PathBuf> {
+                    (**self).data_dirs()
+                }
+            }
+        )*
+    };
+}
+
+impl_base_strategy_for_pointer!(&T, Box<T>, std::sync::Arc<T>);
+
 macro_rules! create_strategies {
     ($native: ty, $base: ty) => {
         /// Returns the current OS’s native [`BaseStrategy`](trait.BaseStrategy.html).
@@ -60,10 +154,16 @@ cfg_if::cfg_if! {
     }
 }
 
+mod any;
 mod apple;
+mod apple_machine;
 mod windows;
+mod windows_machine;
 mod xdg;
 
+pub use any::{AnyBaseStrategy, BaseStrategyKind};
 pub use apple::Apple;
+pub use apple_machine::AppleMachine;
 pub use windows::Windows;
-pub use xdg::Xdg;
+pub use windows_machine::WindowsMachine;
+pub use xdg::{RuntimeDir, RuntimeDirError, Xdg};
@@ -0,0 +1,192 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::{Apple, AppleMachine, BaseStrategy, Windows, WindowsMachine, Xdg};
+use crate::user_dirs::{UserDir, UserDirs};
+use crate::{HomeDirError, ParseStrategyKindError};
+
+/// The name of a [`BaseStrategy`](trait.BaseStrategy.html), which lets you choose one at runtime, e.g. from a command-line flag or an environment variable.
+///
+/// These are parsed from, and displayed as, `xdg`, `apple`, `windows`, `apple-machine` and `windows-machine`. Parsing ignores case.
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategyKind;
+///
+/// assert_eq!("xdg".parse(), Ok(BaseStrategyKind::Xdg));
+/// assert_eq!("Apple".parse(), Ok(BaseStrategyKind::Apple));
+/// assert!("unix".parse::<BaseStrategyKind>().is_err());
+///
+/// assert_eq!(BaseStrategyKind::Windows.to_string(), "windows");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BaseStrategyKind {
+    /// The [`Xdg`](struct.Xdg.html) strategy.
+    Xdg,
+    /// The [`Apple`](struct.Apple.html) strategy.
+    Apple,
+    /// The [`Windows`](struct.Windows.html) strategy.
+    Windows,
+    /// The [`AppleMachine`](struct.AppleMachine.html) strategy.
+    AppleMachine,
+    /// The [`WindowsMachine`](struct.WindowsMachine.html) strategy.
+    WindowsMachine,
+}
+
+impl BaseStrategyKind {
+    /// All of the kinds of base strategies.
+    pub const ALL: [BaseStrategyKind; 5] = [
+        Self::Xdg,
+        Self::Apple,
+        Self::Windows,
+        Self::AppleMachine,
+        Self::WindowsMachine,
+    ];
+
+    /// The name of this kind of strategy, e.g. `xdg`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Xdg => "xdg",
+            Self::Apple => "apple",
+            Self::Windows => "windows",
+            Self::AppleMachine => "apple-machine",
+            Self::WindowsMachine => "windows-machine",
+        }
+    }
+}
+
+impl fmt::Display for BaseStrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BaseStrategyKind {
+    type Err = ParseStrategyKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseStrategyKindError::new(s, Self::ALL.map(Self::as_str)))
+    }
+}
+
+/// Any of the built-in [`BaseStrategy`](trait.BaseStrategy.html)s, chosen at runtime.
+///
+/// ```
+/// use etcetera::base_strategy::{AnyBaseStrategy, BaseStrategy, BaseStrategyKind};
+/// use std::path::Path;
+///
+/// let kind: BaseStrategyKind = "apple".parse().unwrap();
+/// let base_strategy = AnyBaseStrategy::new(kind).unwrap();
+///
+/// assert_eq!(base_strategy.kind(), BaseStrategyKind::Apple);
+/// assert_eq!(
+///     base_strategy.config_dir().strip_prefix(base_strategy.home_dir()),
+///     Ok(Path::new("Library/Preferences/"))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnyBaseStrategy {
+    /// The [`Xdg`](struct.Xdg.html) strategy.
+    Xdg(Xdg),
+    /// The [`Apple`](struct.Apple.html) strategy.
+    Apple(Apple),
+    /// The [`Windows`](struct.Windows.html) strategy.
+    Windows(Windows),
+    /// The [`AppleMachine`](struct.AppleMachine.html) strategy.
+    AppleMachine(AppleMachine),
+    /// The [`WindowsMachine`](struct.WindowsMachine.html) strategy.
+    WindowsMachine(WindowsMachine),
+}
+
+impl AnyBaseStrategy {
+    /// Create a new BaseStrategy of the given kind
+    pub fn new(kind: BaseStrategyKind) -> Result<Self, HomeDirError> {
+        Ok(match kind {
+            BaseStrategyKind::Xdg => Self::Xdg(Xdg::new()?),
+            BaseStrategyKind::Apple => Self::Apple(Apple::new()?),
+            BaseStrategyKind::Windows => Self::Windows(Windows::new()?),
+            BaseStrategyKind::AppleMachine => Self::AppleMachine(AppleMachine::new()),
+            BaseStrategyKind::WindowsMachine => Self::WindowsMachine(WindowsMachine::new()),
+        })
+    }
+
+    /// Gets the kind of this strategy.
+    pub fn kind(&self) -> BaseStrategyKind {
+        match self {
+            Self::Xdg(_) => BaseStrategyKind::Xdg,
+            Self::Apple(_) => BaseStrategyKind::Apple,
+            Self::Windows(_) => BaseStrategyKind::Windows,
+            Self::AppleMachine(_) => BaseStrategyKind::AppleMachine,
+            Self::WindowsMachine(_) => BaseStrategyKind::WindowsMachine,
+        }
+    }
+}
+
+macro_rules! delegate {
+    ($self: ident, $strategy: ident => $expr: expr) => {
+        match $self {
+            AnyBaseStrategy::Xdg($strategy) => $expr,
+            AnyBaseStrategy::Apple($strategy) => $expr,
+            AnyBaseStrategy::Windows($strategy) => $expr,
+            AnyBaseStrategy::AppleMachine($strategy) => $expr,
+            AnyBaseStrategy::WindowsMachine($strategy) => $expr,
+        }
+    };
+}
+
+impl BaseStrategy for AnyBaseStrategy {
+    fn home_dir(&self) -> &Path {
+        delegate!(self, strategy => strategy.home_dir())
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        delegate!(self, strategy => strategy.config_dir())
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        delegate!(self, strategy => strategy.data_dir())
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        delegate!(self, strategy => strategy.cache_dir())
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        delegate!(self, strategy => strategy.state_dir())
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        delegate!(self, strategy => strategy.runtime_dir())
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        delegate!(self, strategy => strategy.bin_dir())
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        delegate!(self, strategy => strategy.is_bin_dir_in_path())
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        delegate!(self, strategy => strategy.config_dirs())
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        delegate!(self, strategy => strategy.data_dirs())
+    }
+}
+
+impl UserDirs for AnyBaseStrategy {
+    fn user_dir(&self, dir: UserDir) -> Option<PathBuf> {
+        match self {
+            Self::Xdg(strategy) => strategy.user_dir(dir),
+            Self::Apple(strategy) => strategy.user_dir(dir),
+            Self::Windows(strategy) => strategy.user_dir(dir),
+            // The machine-wide strategies aren’t about any one user.
+            Self::AppleMachine(_) | Self::WindowsMachine(_) => None,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use crate::HomeDirError;
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
 
 /// This is the strategy created by Apple for use on macOS and iOS devices. It is always used by GUI apps on macOS, and is sometimes used by command-line applications there too. iOS only has GUIs, so all iOS applications follow this strategy. The specification is available [here](https://developer.apple.com/library/archive/documentation/FileManagement/Conceptual/FileSystemProgrammingGuide/FileSystemOverview/FileSystemOverview.html#//apple_ref/doc/uid/TP40010672-CH2-SW1).
 ///
@@ -37,21 +38,49 @@ use crate::HomeDirError;
 ///     base_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     base_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/bin/"))
+/// );
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Apple {
+pub struct Apple<E = ProcessEnvironment> {
     home_dir: PathBuf,
+    env: E,
 }
 impl Apple {
     /// Create a new Apple BaseStrategy
     pub fn new() -> Result<Self, HomeDirError> {
+        Self::with_env(ProcessEnvironment)
+    }
+}
+
+impl Apple<MapEnvironment> {
+    /// Create a new Apple BaseStrategy for the given home directory instead of the current user’s one
+    ///
+    /// The environment of the current process isn’t read.
+    pub fn with_home(home_dir: impl Into<PathBuf>) -> Self {
+        let home_dir = home_dir.into();
+        Self {
+            env: MapEnvironment::new(&home_dir),
+            home_dir,
+        }
+    }
+}
+
+impl<E: Environment> Apple<E> {
+    /// Create a new Apple BaseStrategy that reads from the given environment instead of the current process’s one
+    ///
+    /// The directories only depend on the environment’s home directory, but its `PATH` is used by [`is_bin_dir_in_path`](trait.BaseStrategy.html#method.is_bin_dir_in_path).
+    pub fn with_env(env: E) -> Result<Self, HomeDirError> {
         Ok(Self {
-            home_dir: crate::home_dir()?,
+            home_dir: env.home_dir().ok_or(HomeDirError)?,
+            env,
         })
     }
 }
 
-impl super::BaseStrategy for Apple {
+impl<E: Environment> super::BaseStrategy for Apple<E> {
     fn home_dir(&self) -> &Path {
         &self.home_dir
     }
@@ -75,4 +104,13 @@ impl super::BaseStrategy for Apple {
     fn runtime_dir(&self) -> Option<PathBuf> {
         None
     }
+
+    // Apple doesn’t have a convention for this, so we borrow the XDG one.
+    fn bin_dir(&self) -> PathBuf {
+        self.home_dir.join(".local/bin/")
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.env)
+    }
 }
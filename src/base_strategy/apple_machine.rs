@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
+
+/// This is the machine-wide counterpart to the [`Apple`](struct.Apple.html) strategy, for files that are shared between all users of a computer, e.g. by a launch daemon. It uses the `Library` directory at the root of the file system rather than the one in the user’s home directory.
+///
+/// Like the [`System`](../app_strategy/struct.System.html) app strategy, this one never fails to be created, as daemons often run as a user that has no home directory. Without a home directory, [`home_dir`](trait.BaseStrategy.html#tymethod.home_dir) returns the data directory instead.
+///
+/// ```
+/// use etcetera::base_strategy::AppleMachine;
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// let base_strategy = AppleMachine::new();
+///
+/// assert_eq!(base_strategy.config_dir(), Path::new("/Library/Preferences/"));
+/// assert_eq!(base_strategy.data_dir(), Path::new("/Library/Application Support/"));
+/// assert_eq!(base_strategy.cache_dir(), Path::new("/Library/Caches/"));
+/// assert_eq!(base_strategy.state_dir(), None);
+/// assert_eq!(base_strategy.runtime_dir(), None);
+/// assert_eq!(base_strategy.bin_dir(), Path::new("/usr/local/bin/"));
+///
+/// let base_strategy = AppleMachine::with_env(MapEnvironment::without_home_dir());
+/// assert_eq!(base_strategy.home_dir(), base_strategy.data_dir());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AppleMachine<E = ProcessEnvironment> {
+    home_dir: PathBuf,
+    env: E,
+}
+
+impl AppleMachine {
+    /// Create a new AppleMachine BaseStrategy
+    pub fn new() -> Self {
+        Self::with_env(ProcessEnvironment)
+    }
+}
+
+impl AppleMachine<MapEnvironment> {
+    /// Create a new AppleMachine BaseStrategy for the given home directory instead of the current user’s one
+    ///
+    /// The environment of the current process isn’t read.
+    pub fn with_home(home_dir: impl Into<PathBuf>) -> Self {
+        let home_dir = home_dir.into();
+        Self {
+            env: MapEnvironment::new(&home_dir),
+            home_dir,
+        }
+    }
+}
+
+impl Default for AppleMachine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Environment> AppleMachine<E> {
+    /// Create a new AppleMachine BaseStrategy that reads from the given environment instead of the current process’s one
+    ///
+    /// The directories don’t depend on the environment, but its `PATH` is used by [`is_bin_dir_in_path`](trait.BaseStrategy.html#method.is_bin_dir_in_path).
+    pub fn with_env(env: E) -> Self {
+        Self {
+            home_dir: env
+                .home_dir()
+                .unwrap_or_else(|| PathBuf::from(APPLICATION_SUPPORT)),
+            env,
+        }
+    }
+}
+
+impl<E: Environment> super::BaseStrategy for AppleMachine<E> {
+    fn home_dir(&self) -> &Path {
+        &self.home_dir
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        PathBuf::from("/Library/Preferences/")
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        PathBuf::from(APPLICATION_SUPPORT)
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        PathBuf::from("/Library/Caches/")
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    // This is where most installers put programs for all users.
+    fn bin_dir(&self) -> PathBuf {
+        PathBuf::from("/usr/local/bin/")
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.env)
+    }
+}
+
+const APPLICATION_SUPPORT: &str = "/Library/Application Support/";
@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use crate::HomeDirError;
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
 
 /// This strategy follows Windows’ conventions. It seems that all Windows GUI apps, and some command-line ones follow this pattern. The specification is available [here](https://docs.microsoft.com/en-us/windows/win32/shell/knownfolderid).
 ///
@@ -48,6 +49,10 @@ use crate::HomeDirError;
 ///     base_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     base_strategy.bin_dir().strip_prefix(&home_dir),
+///     Ok(Path::new("AppData/Local/Programs"))
+/// );
 /// ```
 ///
 /// This next example gives the environment variables values:
@@ -105,26 +110,87 @@ use crate::HomeDirError;
 ///     base_strategy.runtime_dir(),
 ///     None
 /// );
+/// assert_eq!(
+///     base_strategy.bin_dir(),
+///     Path::new(cache_path).join("Programs")
+/// );
+/// ```
+///
+/// The strategy can also read from an [`Environment`](../environment/trait.Environment.html) of your choice. Known folders that aren’t set in it are still looked up with the SHGetKnownFolderPath API on Windows:
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::Windows;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// let home_path = if cfg!(windows) {
+///     "C:\\Users\\frobnicator\\"
+/// } else {
+///     "/home/frobnicator/"
+/// };
+/// let data_path = if cfg!(windows) {
+///     "C:\\bar\\"
+/// } else {
+///     "/bar/"
+/// };
+///
+/// let env = MapEnvironment::new(home_path).with_var("APPDATA", data_path);
+///
+/// let base_strategy = Windows::with_env(env).unwrap();
+///
+/// assert_eq!(base_strategy.home_dir(), Path::new(home_path));
+/// assert_eq!(base_strategy.config_dir(), Path::new(data_path));
+/// assert_eq!(base_strategy.data_dir(), Path::new(data_path));
 /// ```
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Windows {
+pub struct Windows<E = ProcessEnvironment> {
     home_dir: PathBuf,
+    pub(crate) env: E,
 }
 
 impl Windows {
     /// Create a new Windows BaseStrategy
     pub fn new() -> Result<Self, HomeDirError> {
+        Self::with_env(ProcessEnvironment)
+    }
+}
+
+impl Windows<MapEnvironment> {
+    /// Create a new Windows BaseStrategy for the given home directory instead of the current user’s one
+    ///
+    /// Neither the environment of the current process nor the current user’s known folders are read, so all directories are inside the given home directory. To set some of them, use [`with_env`](#method.with_env) with a [`MapEnvironment`](../environment/struct.MapEnvironment.html) instead.
+    pub fn with_home(home_dir: impl Into<PathBuf>) -> Self {
+        let home_dir = home_dir.into();
+        // Setting these stops the current user’s known folders from being looked up.
+        let env = MapEnvironment::new(&home_dir)
+            .with_var("APPDATA", home_dir.join("AppData").join("Roaming"))
+            .with_var("LOCALAPPDATA", home_dir.join("AppData").join("Local"));
+        Self { home_dir, env }
+    }
+}
+
+impl<E: Environment> Windows<E> {
+    /// Create a new Windows BaseStrategy that reads from the given environment instead of the current process’s one
+    pub fn with_env(env: E) -> Result<Self, HomeDirError> {
         Ok(Self {
-            home_dir: crate::home_dir()?,
+            home_dir: env.home_dir().ok_or(HomeDirError)?,
+            env,
         })
     }
 
-    fn dir_inner(env: &'static str) -> Option<PathBuf> {
-        std::env::var_os(env)
+    // This is for the strategies that can do without a home directory, and have looked it up themselves.
+    pub(super) fn with_env_and_home(env: E, home_dir: PathBuf) -> Self {
+        Self { home_dir, env }
+    }
+
+    pub(super) fn dir_inner(&self, env_var: &'static str) -> Option<PathBuf> {
+        self.env
+            .var_os(env_var)
             .filter(|s| !s.is_empty())
             .map(PathBuf::from)
-            .or_else(|| Self::dir_crt(env))
+            .or_else(|| Self::dir_crt(env_var))
     }
 
     // Ref: https://github.com/rust-lang/cargo/blob/home-0.5.11/crates/home/src/windows.rs
@@ -139,8 +205,8 @@ impl Windows {
         use windows_sys::Win32::Foundation::S_OK;
         use windows_sys::Win32::System::Com::CoTaskMemFree;
         use windows_sys::Win32::UI::Shell::{
-            FOLDERID_LocalAppData, FOLDERID_RoamingAppData, KF_FLAG_DONT_VERIFY,
-            SHGetKnownFolderPath,
+            FOLDERID_LocalAppData, FOLDERID_ProgramData, FOLDERID_ProgramFiles,
+            FOLDERID_RoamingAppData, KF_FLAG_DONT_VERIFY, SHGetKnownFolderPath,
         };
 
         unsafe extern "C" {
@@ -150,6 +216,8 @@ impl Windows {
         let folder_id = match env {
             "APPDATA" => FOLDERID_RoamingAppData,
             "LOCALAPPDATA" => FOLDERID_LocalAppData,
+            "PROGRAMDATA" => FOLDERID_ProgramData,
+            "PROGRAMFILES" => FOLDERID_ProgramFiles,
             _ => return None,
         };
 
@@ -182,7 +250,7 @@ impl Windows {
     }
 }
 
-impl super::BaseStrategy for Windows {
+impl<E: Environment> super::BaseStrategy for Windows<E> {
     fn home_dir(&self) -> &Path {
         &self.home_dir
     }
@@ -192,11 +260,12 @@ impl super::BaseStrategy for Windows {
     }
 
     fn data_dir(&self) -> PathBuf {
-        Self::dir_inner("APPDATA").unwrap_or_else(|| self.home_dir.join("AppData").join("Roaming"))
+        self.dir_inner("APPDATA")
+            .unwrap_or_else(|| self.home_dir.join("AppData").join("Roaming"))
     }
 
     fn cache_dir(&self) -> PathBuf {
-        Self::dir_inner("LOCALAPPDATA")
+        self.dir_inner("LOCALAPPDATA")
             .unwrap_or_else(|| self.home_dir.join("AppData").join("Local"))
     }
 
@@ -207,4 +276,13 @@ impl super::BaseStrategy for Windows {
     fn runtime_dir(&self) -> Option<PathBuf> {
         None
     }
+
+    // This is where per-user installers, such as the ones made with Inno Setup, put programs.
+    fn bin_dir(&self) -> PathBuf {
+        self.cache_dir().join("Programs")
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.env)
+    }
 }
@@ -0,0 +1,101 @@
+use std::path::{Path, PathBuf};
+
+use super::Windows;
+use crate::environment::{Environment, ProcessEnvironment};
+
+/// This is the machine-wide counterpart to the [`Windows`](struct.Windows.html) strategy, for files that are shared between all users of a computer, e.g. by a service. The specification is available [here](https://docs.microsoft.com/en-us/windows/win32/shell/knownfolderid).
+///
+/// Like the [`System`](../app_strategy/struct.System.html) app strategy, this one never fails to be created, as services often run as a user that has no home directory. Without a home directory, [`home_dir`](trait.BaseStrategy.html#tymethod.home_dir) returns the data directory instead.
+///
+/// The configuration, data and cache directories are all in `ProgramData`, and programs are installed in `Program Files`. Like with the `Windows` strategy, these are read from the `PROGRAMDATA` and `PROGRAMFILES` environment variables, then looked up with the SHGetKnownFolderPath API on Windows, and default to `C:\ProgramData` and `C:\Program Files` otherwise:
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::WindowsMachine;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// let base_strategy = WindowsMachine::with_env(MapEnvironment::new("C:\\Users\\frobnicator"));
+///
+/// if !cfg!(windows) {
+///     assert_eq!(base_strategy.config_dir(), Path::new("C:\\ProgramData"));
+///     assert_eq!(base_strategy.data_dir(), Path::new("C:\\ProgramData"));
+///     assert_eq!(base_strategy.cache_dir(), Path::new("C:\\ProgramData"));
+///     assert_eq!(base_strategy.bin_dir(), Path::new("C:\\Program Files"));
+/// }
+/// assert_eq!(base_strategy.state_dir(), None);
+/// assert_eq!(base_strategy.runtime_dir(), None);
+///
+/// let base_strategy = WindowsMachine::with_env(MapEnvironment::without_home_dir());
+/// assert_eq!(base_strategy.home_dir(), base_strategy.data_dir());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowsMachine<E = ProcessEnvironment> {
+    home_dir: PathBuf,
+    windows: Windows<E>,
+}
+
+impl WindowsMachine {
+    /// Create a new WindowsMachine BaseStrategy
+    pub fn new() -> Self {
+        Self::with_env(ProcessEnvironment)
+    }
+}
+
+impl Default for WindowsMachine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Environment> WindowsMachine<E> {
+    /// Create a new WindowsMachine BaseStrategy that reads from the given environment instead of the current process’s one
+    pub fn with_env(env: E) -> Self {
+        let home_dir = env.home_dir();
+        let windows = Windows::with_env_and_home(env, home_dir.clone().unwrap_or_default());
+        let home_dir = home_dir.unwrap_or_else(|| program_data_dir(&windows));
+        Self { home_dir, windows }
+    }
+}
+
+impl<E: Environment> super::BaseStrategy for WindowsMachine<E> {
+    fn home_dir(&self) -> &Path {
+        &self.home_dir
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.data_dir()
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        program_data_dir(&self.windows)
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.data_dir()
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.windows
+            .dir_inner("PROGRAMFILES")
+            .unwrap_or_else(|| PathBuf::from("C:\\Program Files"))
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.windows.env)
+    }
+}
+
+fn program_data_dir<E: Environment>(windows: &Windows<E>) -> PathBuf {
+    windows
+        .dir_inner("PROGRAMDATA")
+        .unwrap_or_else(|| PathBuf::from("C:\\ProgramData"))
+}
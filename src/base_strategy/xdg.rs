@@ -2,6 +2,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use crate::HomeDirError;
+use crate::environment::{Environment, MapEnvironment, ProcessEnvironment};
 
 /// This strategy implements the [XDG Base Directories Specification](https://specifications.freedesktop.org/basedir/latest/). It is the most common on Linux, but is increasingly being adopted elsewhere.
 ///
@@ -159,21 +160,90 @@ use crate::HomeDirError;
 ///     None
 /// );
 /// ```
+///
+/// Instead of the environment of the current process, the strategy can read from an [`Environment`](../environment/trait.Environment.html) of your choice, which doesn’t require modifying the process’s one:
+///
+/// ```
+/// use etcetera::base_strategy::BaseStrategy;
+/// use etcetera::base_strategy::Xdg;
+/// use etcetera::environment::MapEnvironment;
+/// use std::path::Path;
+///
+/// // We need to conditionally set these to ensure that they are absolute paths both on Windows and other systems.
+/// let data_path = if cfg!(windows) {
+///     "C:\\data\\"
+/// } else {
+///     "/data/"
+/// };
+/// let bin_path = if cfg!(windows) {
+///     "C:\\bin\\"
+/// } else {
+///     "/bin/"
+/// };
+///
+/// let env = MapEnvironment::new("/home/frobnicator")
+///     .with_var("XDG_DATA_HOME", data_path)
+///     .with_var("XDG_CACHE_HOME", "relative/")
+///     .with_var("XDG_BIN_HOME", bin_path);
+///
+/// let base_strategy = Xdg::with_env(env).unwrap();
+///
+/// assert_eq!(base_strategy.home_dir(), Path::new("/home/frobnicator"));
+/// assert_eq!(base_strategy.config_dir(), Path::new("/home/frobnicator/.config/"));
+/// assert_eq!(base_strategy.data_dir(), Path::new(data_path));
+/// assert_eq!(base_strategy.cache_dir(), Path::new("/home/frobnicator/.cache/"));
+/// assert_eq!(base_strategy.runtime_dir(), None);
+/// assert_eq!(base_strategy.bin_dir(), Path::new(bin_path));
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Xdg {
+pub struct Xdg<E = ProcessEnvironment> {
     home_dir: PathBuf,
+    pub(crate) env: E,
 }
 
 impl Xdg {
     /// Create a new Xdg BaseStrategy
     pub fn new() -> Result<Self, HomeDirError> {
+        Self::with_env(ProcessEnvironment)
+    }
+}
+
+impl Xdg<MapEnvironment> {
+    /// Create a new Xdg BaseStrategy for the given home directory instead of the current user’s one
+    ///
+    /// The environment of the current process isn’t read, so the XDG environment variables are ignored and all directories are inside the given home directory. To set some of them, use [`with_env`](#method.with_env) with a [`MapEnvironment`](../environment/struct.MapEnvironment.html) instead.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    /// use std::path::Path;
+    ///
+    /// let base_strategy = Xdg::with_home("/home/frobnicator");
+    ///
+    /// assert_eq!(base_strategy.home_dir(), Path::new("/home/frobnicator"));
+    /// assert_eq!(base_strategy.config_dir(), Path::new("/home/frobnicator/.config/"));
+    /// assert_eq!(base_strategy.data_dir(), Path::new("/home/frobnicator/.local/share/"));
+    /// ```
+    pub fn with_home(home_dir: impl Into<PathBuf>) -> Self {
+        let home_dir = home_dir.into();
+        Self {
+            env: MapEnvironment::new(&home_dir),
+            home_dir,
+        }
+    }
+}
+
+impl<E: Environment> Xdg<E> {
+    /// Create a new Xdg BaseStrategy that reads from the given environment instead of the current process’s one
+    pub fn with_env(env: E) -> Result<Self, HomeDirError> {
         Ok(Self {
-            home_dir: crate::home_dir()?,
+            home_dir: env.home_dir().ok_or(HomeDirError)?,
+            env,
         })
     }
 
-    fn env_var_or_none(env_var: &str) -> Option<PathBuf> {
-        std::env::var_os(env_var).and_then(|path| {
+    fn env_var_or_none(&self, env_var: &str) -> Option<PathBuf> {
+        self.env.var_os(env_var).and_then(|path| {
             let path = PathBuf::from(path);
 
             // Return None if the path obtained from the environment variable isn’t absolute.
@@ -182,11 +252,264 @@ impl Xdg {
     }
 
     fn env_var_or_default(&self, env_var: &str, default: impl AsRef<Path>) -> PathBuf {
-        Self::env_var_or_none(env_var).unwrap_or_else(|| self.home_dir.join(default))
+        self.env_var_or_none(env_var)
+            .unwrap_or_else(|| self.home_dir.join(default))
+    }
+
+    /// Gets the user’s runtime directory, after checking that it meets the requirements of the [XDG Base Directory Specification](https://specifications.freedesktop.org/basedir/latest/).
+    ///
+    /// The directory must be a real directory rather than a symbolic link, it must be owned by the current user, and its access mode must be exactly `0700`.
+    /// The specification’s requirements on the directory’s lifetime can’t be checked, and are not.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::RuntimeDirError;
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    /// use std::os::unix::fs::PermissionsExt;
+    ///
+    /// # if cfg!(miri) { return; } // Miri can’t look up the current user.
+    /// let runtime_path = std::env::temp_dir().join("etcetera-checked-runtime-dir");
+    /// std::fs::create_dir_all(&runtime_path).unwrap();
+    /// std::fs::set_permissions(&runtime_path, std::fs::Permissions::from_mode(0o700)).unwrap();
+    ///
+    /// let env = MapEnvironment::new("/home/frobnicator").with_var("XDG_RUNTIME_DIR", &runtime_path);
+    ///
+    /// let base_strategy = Xdg::with_env(env).unwrap();
+    ///
+    /// assert_eq!(base_strategy.checked_runtime_dir().unwrap(), runtime_path);
+    ///
+    /// // A runtime directory that other users can read from is rejected.
+    /// std::fs::set_permissions(&runtime_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    /// assert!(matches!(
+    ///     base_strategy.checked_runtime_dir(),
+    ///     Err(RuntimeDirError::WrongMode { mode: 0o755 })
+    /// ));
+    ///
+    /// let base_strategy = Xdg::with_home("/home/frobnicator");
+    /// assert!(matches!(
+    ///     base_strategy.checked_runtime_dir(),
+    ///     Err(RuntimeDirError::NotSet)
+    /// ));
+    ///
+    /// std::fs::remove_dir(runtime_path).unwrap();
+    /// ```
+    #[cfg(unix)]
+    pub fn checked_runtime_dir(&self) -> Result<PathBuf, RuntimeDirError> {
+        let runtime_dir = self
+            .env_var_or_none("XDG_RUNTIME_DIR")
+            .ok_or(RuntimeDirError::NotSet)?;
+        check_runtime_dir(&runtime_dir)?;
+        Ok(runtime_dir)
+    }
+
+    /// Gets the user’s runtime directory like [`checked_runtime_dir`](#method.checked_runtime_dir), but falls back to a replacement directory when `XDG_RUNTIME_DIR` isn’t set.
+    ///
+    /// The replacement is a private directory for the current user, named after their user ID, inside the system’s temporary directory.
+    /// It is created with an access mode of `0700` if it doesn’t exist yet, and is then subjected to the same checks as `XDG_RUNTIME_DIR`, so a symbolic link or a directory created by another user is rejected.
+    /// The [XDG Base Directory Specification](https://specifications.freedesktop.org/basedir/latest/) suggests printing a warning when the replacement is used, which [`RuntimeDir::is_fallback`] allows you to do.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::RuntimeDir;
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    ///
+    /// # if cfg!(miri) { return; } // Miri can’t look up the current user.
+    /// let temp_dir = std::env::temp_dir().join("etcetera-runtime-dir-or-fallback");
+    /// std::fs::create_dir_all(&temp_dir).unwrap();
+    ///
+    /// // `XDG_RUNTIME_DIR` isn’t set, and the replacement goes into `TMPDIR`.
+    /// let env = MapEnvironment::new("/home/frobnicator").with_var("TMPDIR", &temp_dir);
+    ///
+    /// let base_strategy = Xdg::with_env(env).unwrap();
+    ///
+    /// let runtime_dir = base_strategy.runtime_dir_or_fallback().unwrap();
+    /// assert!(runtime_dir.is_fallback());
+    /// assert!(runtime_dir.path().starts_with(&temp_dir));
+    /// assert!(runtime_dir.path().is_dir());
+    ///
+    /// // The same directory is reused the next time around.
+    /// assert_eq!(base_strategy.runtime_dir_or_fallback().unwrap(), runtime_dir);
+    ///
+    /// std::fs::remove_dir_all(temp_dir).unwrap();
+    /// ```
+    #[cfg(unix)]
+    pub fn runtime_dir_or_fallback(&self) -> Result<RuntimeDir, RuntimeDirError> {
+        match self.checked_runtime_dir() {
+            Ok(runtime_dir) => Ok(RuntimeDir::Xdg(runtime_dir)),
+            Err(RuntimeDirError::NotSet) => {
+                create_fallback_runtime_dir(&self.env.temp_dir()).map(RuntimeDir::Fallback)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn env_var_paths_or_default(&self, env_var: &str, default: &[&str]) -> Vec<PathBuf> {
+        let paths: Vec<PathBuf> = self
+            .env
+            .var_os(env_var)
+            .map(|paths| {
+                std::env::split_paths(&paths)
+                    // Entries that aren’t absolute are ignored, just like in `env_var_or_none`.
+                    .filter(|path| path.is_absolute())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if paths.is_empty() {
+            default.iter().map(PathBuf::from).collect()
+        } else {
+            paths
+        }
+    }
+}
+
+#[cfg(unix)]
+fn check_runtime_dir(path: &Path) -> Result<(), RuntimeDirError> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    // Collecting the components drops any trailing slash, which would otherwise make
+    // `symlink_metadata` follow a symbolic link.
+    let path: PathBuf = path.components().collect();
+    let metadata = std::fs::symlink_metadata(path).map_err(RuntimeDirError::Io)?;
+
+    if metadata.file_type().is_symlink() {
+        return Err(RuntimeDirError::Symlink);
+    }
+    if !metadata.is_dir() {
+        return Err(RuntimeDirError::NotADirectory);
+    }
+
+    // SAFETY: `getuid` is always successful and has no preconditions.
+    let uid = unsafe { libc::getuid() };
+    if metadata.uid() != uid {
+        return Err(RuntimeDirError::WrongOwner {
+            owner: metadata.uid(),
+            uid,
+        });
     }
+
+    let mode = metadata.permissions().mode() & 0o7777;
+    if mode != 0o700 {
+        return Err(RuntimeDirError::WrongMode { mode });
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_fallback_runtime_dir(temp_dir: &Path) -> Result<PathBuf, RuntimeDirError> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    // SAFETY: `getuid` is always successful and has no preconditions.
+    let uid = unsafe { libc::getuid() };
+    let path = temp_dir.join(format!("xdg-runtime-{uid}"));
+
+    match std::fs::DirBuilder::new().mode(0o700).create(&path) {
+        // The umask may have removed some of the permissions we asked for.
+        Ok(()) => std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o700))
+            .map_err(RuntimeDirError::Io)?,
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(err) => return Err(RuntimeDirError::Io(err)),
+    }
+
+    // Whoever created the directory, it has to pass the same checks as `XDG_RUNTIME_DIR`.
+    check_runtime_dir(&path)?;
+    Ok(path)
+}
+
+/// A runtime directory, along with where it came from.
+///
+/// This is returned by [`Xdg::runtime_dir_or_fallback`](struct.Xdg.html#method.runtime_dir_or_fallback).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RuntimeDir {
+    /// The directory specified by `XDG_RUNTIME_DIR`.
+    Xdg(PathBuf),
+    /// A replacement directory that is used because `XDG_RUNTIME_DIR` isn’t set.
+    Fallback(PathBuf),
+}
+
+impl RuntimeDir {
+    /// Gets the path of the runtime directory.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Xdg(path) | Self::Fallback(path) => path,
+        }
+    }
+
+    /// Converts this into the path of the runtime directory.
+    pub fn into_path(self) -> PathBuf {
+        match self {
+            Self::Xdg(path) | Self::Fallback(path) => path,
+        }
+    }
+
+    /// Returns whether this is a replacement directory, in which case you should consider warning the user.
+    pub fn is_fallback(&self) -> bool {
+        matches!(self, Self::Fallback(_))
+    }
+
+    /// Appends a path of your choice to the runtime directory, keeping track of where it came from.
+    pub fn join(&self, path: impl AsRef<Path>) -> Self {
+        match self {
+            Self::Xdg(runtime_dir) => Self::Xdg(runtime_dir.join(path)),
+            Self::Fallback(runtime_dir) => Self::Fallback(runtime_dir.join(path)),
+        }
+    }
+}
+
+/// This error occurs when the runtime directory doesn’t meet the requirements of the [XDG Base Directory Specification](https://specifications.freedesktop.org/basedir/latest/).
+#[derive(Debug)]
+pub enum RuntimeDirError {
+    /// `XDG_RUNTIME_DIR` is unset, or isn’t an absolute path.
+    NotSet,
+    /// The runtime directory could not be inspected.
+    Io(std::io::Error),
+    /// The runtime directory is a symbolic link.
+    Symlink,
+    /// The runtime directory is not a directory.
+    NotADirectory,
+    /// The runtime directory is not owned by the current user.
+    WrongOwner {
+        /// The user ID of the runtime directory’s owner.
+        owner: u32,
+        /// The user ID of the current user.
+        uid: u32,
+    },
+    /// The runtime directory’s access mode isn’t `0700`.
+    WrongMode {
+        /// The runtime directory’s access mode.
+        mode: u32,
+    },
 }
 
-impl super::BaseStrategy for Xdg {
+impl std::fmt::Display for RuntimeDirError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotSet => write!(f, "XDG_RUNTIME_DIR is not set to an absolute path"),
+            Self::Io(err) => write!(f, "could not inspect runtime directory: {err}"),
+            Self::Symlink => write!(f, "runtime directory is a symbolic link"),
+            Self::NotADirectory => write!(f, "runtime directory is not a directory"),
+            Self::WrongOwner { owner, uid } => write!(
+                f,
+                "runtime directory is owned by user {owner} instead of the current user {uid}"
+            ),
+            Self::WrongMode { mode } => {
+                write!(f, "runtime directory has mode {mode:04o} instead of 0700")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuntimeDirError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<E: Environment> super::BaseStrategy for Xdg<E> {
     fn home_dir(&self) -> &Path {
         &self.home_dir
     }
@@ -208,6 +531,77 @@ impl super::BaseStrategy for Xdg {
     }
 
     fn runtime_dir(&self) -> Option<PathBuf> {
-        Self::env_var_or_none("XDG_RUNTIME_DIR")
+        self.env_var_or_none("XDG_RUNTIME_DIR")
+    }
+
+    // `XDG_BIN_HOME` isn’t part of the specification yet, but `~/.local/bin` is.
+    fn bin_dir(&self) -> PathBuf {
+        self.env_var_or_default("XDG_BIN_HOME", ".local/bin/")
+    }
+
+    fn is_bin_dir_in_path(&self) -> bool {
+        crate::is_in_path(&self.bin_dir(), &self.env)
+    }
+
+    /// Gets the system-wide configuration directories, in order of decreasing precedence.
+    ///
+    /// These are read from the list in `XDG_CONFIG_DIRS` (separated by `:` on Unix and `;` on Windows), and default to `/etc/xdg` when it is unset or contains no absolute paths.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    /// use std::path::PathBuf;
+    ///
+    /// let base_strategy = Xdg::with_home("/home/frobnicator");
+    /// assert_eq!(base_strategy.config_dirs(), [PathBuf::from("/etc/xdg/")]);
+    ///
+    /// // We need to conditionally set these to ensure that they are absolute paths both on Windows and other systems.
+    /// let first_path = if cfg!(windows) {
+    ///     "C:\\foo\\"
+    /// } else {
+    ///     "/foo/"
+    /// };
+    /// let second_path = if cfg!(windows) {
+    ///     "C:\\bar\\"
+    /// } else {
+    ///     "/bar/"
+    /// };
+    ///
+    /// // The relative entry in the middle is ignored.
+    /// let env = MapEnvironment::new("/home/frobnicator").with_var(
+    ///     "XDG_CONFIG_DIRS",
+    ///     std::env::join_paths([first_path, "relative/", second_path]).unwrap(),
+    /// );
+    /// let base_strategy = Xdg::with_env(env).unwrap();
+    /// assert_eq!(
+    ///     base_strategy.config_dirs(),
+    ///     [PathBuf::from(first_path), PathBuf::from(second_path)]
+    /// );
+    /// ```
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.env_var_paths_or_default("XDG_CONFIG_DIRS", &["/etc/xdg/"])
+    }
+
+    /// Gets the system-wide data directories, in order of decreasing precedence.
+    ///
+    /// These are read from the list in `XDG_DATA_DIRS` (separated by `:` on Unix and `;` on Windows), and default to `/usr/local/share` and `/usr/share` when it is unset or contains no absolute paths.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::BaseStrategy;
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    /// use std::path::PathBuf;
+    ///
+    /// let env = MapEnvironment::new("/home/frobnicator").with_var("XDG_DATA_DIRS", "relative/");
+    /// let base_strategy = Xdg::with_env(env).unwrap();
+    ///
+    /// assert_eq!(
+    ///     base_strategy.data_dirs(),
+    ///     [PathBuf::from("/usr/local/share/"), PathBuf::from("/usr/share/")]
+    /// );
+    /// ```
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.env_var_paths_or_default("XDG_DATA_DIRS", &["/usr/local/share/", "/usr/share/"])
     }
 }
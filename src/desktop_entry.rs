@@ -0,0 +1,306 @@
+//! This generates desktop entries, which describe how to launch an application, as specified by the [Desktop Entry Specification](https://specifications.freedesktop.org/desktop-entry-spec/latest/).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A desktop entry for an application.
+///
+/// Its [`Display`](https://doc.rust-lang.org/std/fmt/trait.Display.html) implementation produces the contents of a `.desktop` file, with all values escaped as the specification requires.
+///
+/// ```
+/// use etcetera::desktop_entry::{DesktopEntry, FieldCode};
+///
+/// let mut entry = DesktopEntry::new("Frobnicator Plus", ["/opt/frobnicator plus/frobnicate", "--discount=100%"]);
+/// entry.localized_names.insert("de".to_string(), "Frobnikator Plus".to_string());
+/// entry.comment = Some("Frobnicates your files".to_string());
+/// entry.icon = Some("org.acme-corp.Frobnicator-Plus".to_string());
+/// entry.field_code = Some(FieldCode::Files);
+/// entry.categories = vec!["Utility".to_string()];
+///
+/// assert_eq!(
+///     entry.to_string(),
+///     r#"[Desktop Entry]
+/// Type=Application
+/// Version=1.5
+/// Name=Frobnicator Plus
+/// Name[de]=Frobnikator Plus
+/// Comment=Frobnicates your files
+/// Icon=org.acme-corp.Frobnicator-Plus
+/// Exec="/opt/frobnicator plus/frobnicate" --discount=100%% %F
+/// Terminal=false
+/// Categories=Utility;
+/// "#
+/// );
+/// ```
+///
+/// Arguments are quoted when they contain special characters, and backslashes are escaped twice, once for the quoting and once more for the file format:
+///
+/// ```
+/// use etcetera::desktop_entry::DesktopEntry;
+///
+/// let entry = DesktopEntry::new("Frobnicator", ["frobnicate", r#"say "hi""#, r"C:\frob"]);
+///
+/// assert!(entry.to_string().contains(r#"Exec=frobnicate "say \\"hi\\"" "C:\\\\frob""#));
+/// ```
+///
+/// Keys and locales that the specification doesn’t allow are left out, so that they can’t add entries of their own. Use [`validate`](#method.validate) to find them instead.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct DesktopEntry {
+    /// The name of the application.
+    pub name: String,
+    /// Translations of the name of the application, keyed by locale, e.g. `de` or `pt_BR`.
+    pub localized_names: BTreeMap<String, String>,
+    /// A tooltip for the application.
+    pub comment: Option<String>,
+    /// Translations of the tooltip for the application, keyed by locale.
+    pub localized_comments: BTreeMap<String, String>,
+    /// The name of the application’s icon in the icon theme, or the absolute path to it.
+    pub icon: Option<String>,
+    /// The program to run and its arguments, which are quoted and escaped as needed.
+    pub exec: Vec<String>,
+    /// The field code to append to the arguments in `exec`, which is replaced by the files or URLs to open.
+    pub field_code: Option<FieldCode>,
+    /// Whether the application runs in a terminal.
+    pub terminal: bool,
+    /// The [categories](https://specifications.freedesktop.org/menu-spec/latest/category-registry.html) the application belongs to.
+    pub categories: Vec<String>,
+    /// The MIME types the application can open.
+    pub mime_types: Vec<String>,
+    /// Any other keys, such as the ones starting with `X-`.
+    pub extra_keys: Vec<(String, String)>,
+}
+
+impl DesktopEntry {
+    /// Creates a desktop entry for an application with the given name, which is launched by running the given program and arguments.
+    pub fn new<I, S>(name: impl Into<String>, exec: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            exec: exec.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Checks that all of the locales and extra keys are allowed by the specification, and that no value contains control characters other than newlines, carriage returns and tabs, which are escaped.
+    ///
+    /// ```
+    /// use etcetera::desktop_entry::DesktopEntry;
+    ///
+    /// let mut entry = DesktopEntry::new("Frobnicator", ["frobnicate"]);
+    /// entry.extra_keys.push(("X-Frobnicator-Level".to_string(), "11".to_string()));
+    /// assert!(entry.validate().is_ok());
+    ///
+    /// entry.extra_keys.push(("X-Level=11\nExec".to_string(), "sh -c injected".to_string()));
+    /// assert!(entry.validate().is_err());
+    /// assert!(!entry.to_string().contains("injected"));
+    /// ```
+    pub fn validate(&self) -> Result<(), InvalidDesktopEntryError> {
+        for locale in self
+            .localized_names
+            .keys()
+            .chain(self.localized_comments.keys())
+        {
+            if !is_valid_locale(locale) {
+                return Err(InvalidDesktopEntryError::new(format!(
+                    "invalid locale `{}`",
+                    locale.escape_debug()
+                )));
+            }
+        }
+        for (key, _) in &self.extra_keys {
+            if !is_valid_key(key) {
+                return Err(InvalidDesktopEntryError::new(format!(
+                    "invalid key `{}`",
+                    key.escape_debug()
+                )));
+            }
+        }
+
+        let values = std::iter::once(&self.name)
+            .chain(self.localized_names.values())
+            .chain(&self.comment)
+            .chain(self.localized_comments.values())
+            .chain(&self.icon)
+            .chain(&self.exec)
+            .chain(&self.categories)
+            .chain(&self.mime_types)
+            .chain(self.extra_keys.iter().map(|(_, value)| value));
+        for value in values {
+            if value.chars().any(is_unescapable_control) {
+                return Err(InvalidDesktopEntryError::new(format!(
+                    "invalid value `{}`",
+                    value.escape_debug()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// This error occurs when a [`DesktopEntry`](struct.DesktopEntry.html) contains a key, locale or value that the specification doesn’t allow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDesktopEntryError {
+    message: String,
+}
+
+impl InvalidDesktopEntryError {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl fmt::Display for InvalidDesktopEntryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid desktop entry: {}", self.message)
+    }
+}
+
+impl std::error::Error for InvalidDesktopEntryError {}
+
+/// A placeholder in the `Exec` key, which is replaced by the files or URLs the application is asked to open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FieldCode {
+    /// A single file, i.e. `%f`.
+    File,
+    /// A list of files, i.e. `%F`.
+    Files,
+    /// A single URL, i.e. `%u`.
+    Url,
+    /// A list of URLs, i.e. `%U`.
+    Urls,
+}
+
+impl FieldCode {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::File => "%f",
+            Self::Files => "%F",
+            Self::Url => "%u",
+            Self::Urls => "%U",
+        }
+    }
+}
+
+impl fmt::Display for DesktopEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[Desktop Entry]")?;
+        writeln!(f, "Type=Application")?;
+        writeln!(f, "Version=1.5")?;
+        writeln!(f, "Name={}", escape_string(&self.name))?;
+        for (locale, name) in self
+            .localized_names
+            .iter()
+            .filter(|(locale, _)| is_valid_locale(locale))
+        {
+            writeln!(f, "Name[{locale}]={}", escape_string(name))?;
+        }
+        if let Some(comment) = &self.comment {
+            writeln!(f, "Comment={}", escape_string(comment))?;
+        }
+        for (locale, comment) in self
+            .localized_comments
+            .iter()
+            .filter(|(locale, _)| is_valid_locale(locale))
+        {
+            writeln!(f, "Comment[{locale}]={}", escape_string(comment))?;
+        }
+        if let Some(icon) = &self.icon {
+            writeln!(f, "Icon={}", escape_string(icon))?;
+        }
+
+        let mut exec: Vec<String> = self.exec.iter().map(|arg| quote_exec_arg(arg)).collect();
+        exec.extend(
+            self.field_code
+                .map(|field_code| field_code.as_str().to_string()),
+        );
+        writeln!(f, "Exec={}", escape_string(&exec.join(" ")))?;
+
+        writeln!(f, "Terminal={}", self.terminal)?;
+        if !self.categories.is_empty() {
+            writeln!(f, "Categories={}", escape_list(&self.categories))?;
+        }
+        if !self.mime_types.is_empty() {
+            writeln!(f, "MimeType={}", escape_list(&self.mime_types))?;
+        }
+        for (key, value) in self.extra_keys.iter().filter(|(key, _)| is_valid_key(key)) {
+            writeln!(f, "{key}={}", escape_string(value))?;
+        }
+
+        Ok(())
+    }
+}
+
+// The characters that can’t appear in an argument of the `Exec` key without quoting it.
+const EXEC_RESERVED_CHARS: &str = "\"'\\><~|&;$*?#()`";
+
+// Quotes an argument of the `Exec` key, before the general escaping of strings is applied.
+fn quote_exec_arg(arg: &str) -> String {
+    // A literal percent sign would otherwise be read as the start of a field code.
+    let arg = arg.replace('%', "%%");
+
+    let needs_quoting = arg.is_empty()
+        || arg
+            .chars()
+            .any(|c| c.is_whitespace() || EXEC_RESERVED_CHARS.contains(c));
+    if !needs_quoting {
+        return arg;
+    }
+
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('"');
+    for c in arg.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Keys may only contain the characters `A-Za-z0-9-`.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+// Locales have the form `lang_COUNTRY.ENCODING@MODIFIER`, where all but `lang` are optional.
+fn is_valid_locale(locale: &str) -> bool {
+    !locale.is_empty()
+        && locale
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '@' | '-'))
+}
+
+// Newlines, tabs and carriage returns can be escaped, but no other control characters.
+fn is_unescapable_control(c: char) -> bool {
+    c.is_control() && !matches!(c, '\n' | '\t' | '\r')
+}
+
+fn escape_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            // Leading spaces would otherwise be stripped.
+            ' ' if i == 0 => escaped.push_str("\\s"),
+            // Other control characters aren’t allowed at all.
+            c if is_unescapable_control(c) => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("{};", escape_string(value).replace(';', "\\;")))
+        .collect()
+}
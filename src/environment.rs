@@ -0,0 +1,157 @@
+//! These provide the environment variables and home directory that the strategies read from.
+//!
+//! By default, the strategies read from the environment of the current process. You can give them any other [`Environment`](trait.Environment.html) instead, which lets you compute directories for another environment without modifying the process’s one.
+//!
+//! ```
+//! use etcetera::base_strategy::{BaseStrategy, Xdg};
+//! use etcetera::environment::MapEnvironment;
+//! use std::path::Path;
+//!
+//! // We need to conditionally set this to ensure that it is an absolute path both on Windows and other systems.
+//! let config_path = if cfg!(windows) {
+//!     "C:\\config\\"
+//! } else {
+//!     "/config/"
+//! };
+//!
+//! let env = MapEnvironment::new("/home/frobnicator").with_var("XDG_CONFIG_HOME", config_path);
+//!
+//! let base_strategy = Xdg::with_env(&env).unwrap();
+//!
+//! assert_eq!(base_strategy.home_dir(), Path::new("/home/frobnicator"));
+//! assert_eq!(base_strategy.config_dir(), Path::new(config_path));
+//! assert_eq!(base_strategy.data_dir(), Path::new("/home/frobnicator/.local/share/"));
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Provides the environment variables and home directory that the strategies read from.
+pub trait Environment {
+    /// Gets the value of the environment variable `key`, or `None` if it isn’t set.
+    fn var_os(&self, key: &str) -> Option<OsString>;
+
+    /// Gets the home directory of the current user, or `None` if it can’t be located.
+    fn home_dir(&self) -> Option<PathBuf>;
+
+    /// Gets the directory for temporary files.
+    ///
+    /// By default, this is `TMPDIR` if it is set to an absolute path, and `/tmp` otherwise.
+    fn temp_dir(&self) -> PathBuf {
+        self.var_os("TMPDIR")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+    }
+}
+
+impl<T: Environment + ?Sized> Environment for &T {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        (**self).var_os(key)
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        (**self).home_dir()
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        (**self).temp_dir()
+    }
+}
+
+/// The environment of the current process, which the strategies use by default.
+///
+/// The home directory is determined by [`std::env::home_dir`](https://doc.rust-lang.org/std/env/fn.home_dir.html), and the directory for temporary files by [`std::env::temp_dir`](https://doc.rust-lang.org/std/env/fn.temp_dir.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ProcessEnvironment;
+
+impl Environment for ProcessEnvironment {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        std::env::home_dir()
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        std::env::temp_dir()
+    }
+}
+
+/// An environment whose variables are stored in a [`BTreeMap`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html), with a home directory of your choice.
+///
+/// This is useful in tests, as it doesn’t require modifying the environment of the current process, which is `unsafe` in multithreaded programs.
+///
+/// Like the environment of the current process, it can be compared and hashed, and so can the strategies that read from it:
+///
+/// ```
+/// use etcetera::base_strategy::Xdg;
+/// use std::collections::HashSet;
+///
+/// let mut base_strategies = HashSet::new();
+/// base_strategies.insert(Xdg::with_home("/home/frobnicator"));
+/// assert!(base_strategies.contains(&Xdg::with_home("/home/frobnicator")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MapEnvironment {
+    vars: BTreeMap<String, OsString>,
+    home_dir: Option<PathBuf>,
+}
+
+impl MapEnvironment {
+    /// Creates an environment without any variables, whose home directory is `home_dir`.
+    pub fn new(home_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            vars: BTreeMap::new(),
+            home_dir: Some(home_dir.into()),
+        }
+    }
+
+    /// Creates an environment without any variables or home directory.
+    pub fn without_home_dir() -> Self {
+        Self::default()
+    }
+
+    /// Sets the environment variable `key` to `value`, and returns the environment.
+    pub fn with_var(mut self, key: impl Into<String>, value: impl Into<OsString>) -> Self {
+        self.set_var(key, value);
+        self
+    }
+
+    /// Sets the environment variable `key` to `value`.
+    pub fn set_var(&mut self, key: impl Into<String>, value: impl Into<OsString>) {
+        self.vars.insert(key.into(), value.into());
+    }
+
+    /// Removes the environment variable `key`.
+    pub fn remove_var(&mut self, key: &str) {
+        self.vars.remove(key);
+    }
+}
+
+impl From<HashMap<String, OsString>> for MapEnvironment {
+    /// Creates an environment with the given variables, whose home directory is taken from `HOME`, or from `USERPROFILE` if that isn’t set.
+    fn from(vars: HashMap<String, OsString>) -> Self {
+        let home_dir = ["HOME", "USERPROFILE"]
+            .iter()
+            .filter_map(|key| vars.get(*key))
+            .find(|home_dir| !home_dir.is_empty())
+            .map(PathBuf::from);
+        Self {
+            vars: vars.into_iter().collect(),
+            home_dir,
+        }
+    }
+}
+
+impl Environment for MapEnvironment {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.vars.get(key).cloned()
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        self.home_dir.clone()
+    }
+}
@@ -52,10 +52,70 @@
 //! let runtime_dir = strategy.runtime_dir();
 //! ```
 //!
+//! ## UserDirs
+//! If you want to get the path to one of the user’s well-known folders, such as their downloads folder, you can use the [`UserDirs`](user_dirs/trait.UserDirs.html) trait, which is implemented by the base strategies.
+//! On Linux, this follows the folders the user has configured with [xdg-user-dirs](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/), whose names are often in the user’s own language.
+//!
+//! ```
+//! use etcetera::{choose_base_strategy, UserDirs};
+//!
+//! let strategy = choose_base_strategy().unwrap();
+//!
+//! let desktop_dir = strategy.desktop_dir();
+//! let download_dir = strategy.download_dir();
+//! let documents_dir = strategy.documents_dir();
+//! ```
+//!
+//! ## ShellDirs
+//! If you’re developing a CLI tool, the [`ShellDirs`](shell/trait.ShellDirs.html) trait gives you the directories in which to install shell completions and man pages for the current user.
+//!
+//! ```
+//! use etcetera::{choose_base_strategy, ShellDirs};
+//!
+//! let strategy = choose_base_strategy().unwrap();
+//!
+//! let bash_completion_dir = strategy.bash_completion_dir();
+//! let man_dir = strategy.man_dir(1);
+//! ```
+//!
+//! ## Environment
+//! The strategies read the environment variables and home directory of the current process by default.
+//! In tests, or to compute the directories of another environment, you can construct them with `with_env` instead, passing an [`Environment`](environment/trait.Environment.html) such as a [`MapEnvironment`](environment/struct.MapEnvironment.html).
+//!
+//! ## ResolvedDirs
+//! The strategies read the environment every time you ask them for a directory. If you’d rather determine the directories once, at startup, you can turn any strategy into a [`ResolvedDirs`](resolved_dirs/struct.ResolvedDirs.html), which is a plain value that can be compared, hashed and passed around.
+//!
+//! ```
+//! use etcetera::{choose_base_strategy, ResolvedDirs};
+//!
+//! let dirs = ResolvedDirs::from_base_strategy(&choose_base_strategy().unwrap());
+//!
+//! let config_dir = &dirs.config_dir;
+//! ```
+//!
+//! ## Choosing a Strategy at Runtime
+//! If you’d like your users to pick a strategy, e.g. with a command-line flag, you can parse its name into an [`AppStrategyKind`](app_strategy/enum.AppStrategyKind.html) and create an [`AnyAppStrategy`](app_strategy/enum.AnyAppStrategy.html) from it. The same goes for [`BaseStrategyKind`](base_strategy/enum.BaseStrategyKind.html) and [`AnyBaseStrategy`](base_strategy/enum.AnyBaseStrategy.html).
+//!
+//! ```
+//! use etcetera::app_strategy::{AnyAppStrategy, AppStrategyKind};
+//! use etcetera::{AppStrategy, AppStrategyArgs};
+//!
+//! let kind: AppStrategyKind = "unix".parse().unwrap();
+//! let strategy = AnyAppStrategy::new(kind, AppStrategyArgs {
+//!     top_level_domain: "org".to_string(),
+//!     author: "Acme Corp".to_string(),
+//!     app_name: "Frobnicator Plus".to_string(),
+//! }).unwrap();
+//!
+//! let config_dir = strategy.config_dir(); // produces ~/.frobnicator-plus/
+//! ```
+//!
 //! ## Native Strategy
 //!
 //! `choose_base_strategy()` and `choose_app_strategy()` will use the `XDG` strategy on Linux & macOS, and the `Windows` strategy on Windows.
 //! This is used by most CLI tools & some GUI tools on each platform.
+//! If your application may also be installed as a Flatpak or a snap, use [`detect_app_strategy()`](app_strategy/fn.detect_app_strategy.html) instead, which switches to the [`Flatpak`](app_strategy/struct.Flatpak.html) or [`Snap`](app_strategy/struct.Snap.html) strategy when it runs inside one.
+//! If you ship a self-contained build, e.g. for a USB stick, you can opt in to the [`Portable`](app_strategy/struct.Portable.html) strategy with [`AnyAppStrategy::detect_portable()`](app_strategy/enum.AnyAppStrategy.html#method.detect_portable), which keeps everything next to the executable once a `<app>.portable` file is put there.
 //!
 //! If you're developing a GUI application, you might want to use the "Standard directories" on macOS by using `choose_native_strategy()` instead.
 //! Note that if your application expects the user to modify the configuration files, you should still prefer the `XDG` strategy on macOS.
@@ -64,6 +124,9 @@
 //!
 //! You aren’t limited to the built-in conventions – you can implement the relevant traits yourself. Please consider contributing these back, as the more preset conventions there are, the better.
 //!
+//! # Features
+//! - `serde`: implements `Serialize` and `Deserialize` for [`AppStrategyArgs`](app_strategy/struct.AppStrategyArgs.html) and [`ResolvedDirs`](resolved_dirs/struct.ResolvedDirs.html).
+//!
 //! # More Examples
 //! Say you were a hardened Unix veteran, and didn’t want to have any of this XDG nonsense, clutter in the home directory be damned! Instead of using `choose_app_strategy` or `choose_base_strategy`, you can pick a strategy yourself. Here’s an example using the [`Unix`](app_strategy/struct.Unix.html) strategy – see its documentation to see what kind of folder structures it produces:
 //!
@@ -103,16 +166,33 @@
 #![warn(missing_docs, rust_2018_idioms, missing_debug_implementations)]
 
 pub mod app_strategy;
+pub mod autostart;
 pub mod base_strategy;
+pub mod desktop_entry;
+pub mod environment;
+pub mod resolved_dirs;
+pub mod shell;
+pub mod systemd;
+pub mod trash;
+pub mod user_dirs;
 
 pub use app_strategy::{AppStrategy, AppStrategyArgs, choose_app_strategy};
 pub use base_strategy::{BaseStrategy, choose_base_strategy};
+pub use resolved_dirs::ResolvedDirs;
+pub use shell::ShellDirs;
+pub use user_dirs::{UserDir, UserDirs};
 
 /// A convenience function that wraps the [`home_dir`](https://doc.rust-lang.org/std/env/fn.home_dir.html) function from the standard library.
 pub fn home_dir() -> Result<std::path::PathBuf, HomeDirError> {
     std::env::home_dir().ok_or(HomeDirError)
 }
 
+// Returns whether `dir` is one of the directories listed in the environment’s `PATH`.
+fn is_in_path(dir: &std::path::Path, env: &impl environment::Environment) -> bool {
+    env.var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|path| path == dir))
+}
+
 /// This error occurs when the home directory cannot be located.
 #[derive(Debug)]
 pub struct HomeDirError;
@@ -124,3 +204,32 @@ impl std::fmt::Display for HomeDirError {
 }
 
 impl std::error::Error for HomeDirError {}
+
+/// This error occurs when a string isn’t the name of a known strategy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStrategyKindError {
+    name: String,
+    expected: Vec<&'static str>,
+}
+
+impl ParseStrategyKindError {
+    fn new(name: &str, expected: impl IntoIterator<Item = &'static str>) -> Self {
+        Self {
+            name: name.to_string(),
+            expected: expected.into_iter().collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseStrategyKindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown strategy `{}`, expected one of: {}",
+            self.name,
+            self.expected.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseStrategyKindError {}
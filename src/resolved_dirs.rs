@@ -0,0 +1,150 @@
+//! This captures the directories of a strategy once, so that they don’t change while your application is running.
+
+use std::path::{Path, PathBuf};
+
+use crate::app_strategy::AppStrategy;
+use crate::base_strategy::BaseStrategy;
+
+/// The directories of a [`BaseStrategy`](../base_strategy/trait.BaseStrategy.html) or an [`AppStrategy`](../app_strategy/trait.AppStrategy.html), resolved at a single point in time.
+///
+/// The strategies read the environment every time one of their methods is called, so their directories change if the environment does. A `ResolvedDirs` never changes, and can be passed around in place of the strategy it was created from, as it implements [`AppStrategy`](../app_strategy/trait.AppStrategy.html). It doesn’t implement `BaseStrategy` as well, as the methods of both traits would then be ambiguous.
+///
+/// ```
+/// use etcetera::app_strategy::{AppStrategyArgs, Xdg};
+/// use etcetera::environment::MapEnvironment;
+/// use etcetera::{AppStrategy, BaseStrategy, ResolvedDirs};
+/// use std::path::Path;
+///
+/// // We need to conditionally set this to ensure that it is an absolute path both on Windows and other systems.
+/// let config_path = if cfg!(windows) {
+///     "C:\\config\\"
+/// } else {
+///     "/config/"
+/// };
+///
+/// let mut env = MapEnvironment::new("/home/frobnicator").with_var("XDG_CONFIG_HOME", config_path);
+///
+/// let args = AppStrategyArgs {
+///     top_level_domain: "org".to_string(),
+///     author: "Acme Corp".to_string(),
+///     app_name: "Frobnicator Plus".to_string(),
+/// };
+/// let dirs = ResolvedDirs::from_app_strategy(&Xdg::with_env(args.clone(), &env).unwrap());
+///
+/// env.remove_var("XDG_CONFIG_HOME");
+/// let app_strategy = Xdg::with_env(args, &env).unwrap();
+///
+/// // The resolved directories are unaffected by the change to the environment.
+/// assert_eq!(dirs.config_dir(), Path::new(config_path).join("frobnicator-plus"));
+/// assert_ne!(dirs.config_dir(), app_strategy.config_dir());
+///
+/// // Base strategies can be resolved too.
+/// let base_strategy = etcetera::base_strategy::Xdg::with_env(&env).unwrap();
+/// let dirs = ResolvedDirs::from_base_strategy(&base_strategy);
+/// assert_eq!(dirs.config_dir(), base_strategy.config_dir());
+/// ```
+///
+/// With the `serde` feature enabled, the directories can be handed to another process, e.g. as JSON:
+///
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// use etcetera::{choose_base_strategy, ResolvedDirs};
+///
+/// let dirs = ResolvedDirs::from_base_strategy(&choose_base_strategy().unwrap());
+///
+/// let json = serde_json::to_string(&dirs).unwrap();
+/// assert_eq!(serde_json::from_str::<ResolvedDirs>(&json).unwrap(), dirs);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResolvedDirs {
+    /// The home directory of the current user.
+    pub home_dir: PathBuf,
+    /// The configuration directory.
+    pub config_dir: PathBuf,
+    /// The data directory.
+    pub data_dir: PathBuf,
+    /// The cache directory.
+    pub cache_dir: PathBuf,
+    /// The state directory, if the strategy supports one.
+    pub state_dir: Option<PathBuf>,
+    /// The runtime directory, if the strategy supports one.
+    pub runtime_dir: Option<PathBuf>,
+    /// The directory for executables.
+    pub bin_dir: PathBuf,
+    /// The system-wide configuration directories, in order of decreasing precedence.
+    pub config_dirs: Vec<PathBuf>,
+    /// The system-wide data directories, in order of decreasing precedence.
+    pub data_dirs: Vec<PathBuf>,
+}
+
+impl ResolvedDirs {
+    /// Resolves the directories of a [`BaseStrategy`](../base_strategy/trait.BaseStrategy.html).
+    pub fn from_base_strategy<S: BaseStrategy + ?Sized>(strategy: &S) -> Self {
+        Self {
+            home_dir: strategy.home_dir().to_path_buf(),
+            config_dir: strategy.config_dir(),
+            data_dir: strategy.data_dir(),
+            cache_dir: strategy.cache_dir(),
+            state_dir: strategy.state_dir(),
+            runtime_dir: strategy.runtime_dir(),
+            bin_dir: strategy.bin_dir(),
+            config_dirs: strategy.config_dirs(),
+            data_dirs: strategy.data_dirs(),
+        }
+    }
+
+    /// Resolves the directories of an [`AppStrategy`](../app_strategy/trait.AppStrategy.html).
+    pub fn from_app_strategy<S: AppStrategy + ?Sized>(strategy: &S) -> Self {
+        Self {
+            home_dir: strategy.home_dir().to_path_buf(),
+            config_dir: strategy.config_dir(),
+            data_dir: strategy.data_dir(),
+            cache_dir: strategy.cache_dir(),
+            state_dir: strategy.state_dir(),
+            runtime_dir: strategy.runtime_dir(),
+            bin_dir: strategy.bin_dir(),
+            config_dirs: strategy.config_dirs(),
+            data_dirs: strategy.data_dirs(),
+        }
+    }
+}
+
+impl AppStrategy for ResolvedDirs {
+    fn home_dir(&self) -> &Path {
+        &self.home_dir
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.config_dir.clone()
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        self.data_dir.clone()
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.cache_dir.clone()
+    }
+
+    fn state_dir(&self) -> Option<PathBuf> {
+        self.state_dir.clone()
+    }
+
+    fn runtime_dir(&self) -> Option<PathBuf> {
+        self.runtime_dir.clone()
+    }
+
+    fn bin_dir(&self) -> PathBuf {
+        self.bin_dir.clone()
+    }
+
+    fn config_dirs(&self) -> Vec<PathBuf> {
+        self.config_dirs.clone()
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        self.data_dirs.clone()
+    }
+}
@@ -0,0 +1,114 @@
+//! These provide the directories in which command-line applications install their shell completions and man pages.
+
+use std::path::{Path, PathBuf};
+
+use crate::base_strategy::BaseStrategy;
+
+/// Provides the directories in which command-line applications install their shell completions and man pages.
+///
+/// The user-level directories are inside the user’s [data directory](../base_strategy/trait.BaseStrategy.html#tymethod.data_dir), and the system-wide ones are inside each of the [system-wide data directories](../base_strategy/trait.BaseStrategy.html#method.data_dirs).
+/// These are the locations that the shells and `man` search when they follow the XDG conventions, so they are mostly useful with the [`Xdg`](../base_strategy/struct.Xdg.html) strategy.
+///
+/// This trait is implemented for every [`BaseStrategy`](../base_strategy/trait.BaseStrategy.html).
+///
+/// ```
+/// use etcetera::base_strategy::Xdg;
+/// use etcetera::shell::ShellDirs;
+/// use std::path::{Path, PathBuf};
+///
+/// let home_dir = Path::new("/home/frobnicator");
+///
+/// let base_strategy = Xdg::with_home(home_dir);
+///
+/// assert_eq!(
+///     base_strategy.bash_completion_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/share/bash-completion/completions"))
+/// );
+/// assert_eq!(
+///     base_strategy.zsh_completion_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/share/zsh/site-functions"))
+/// );
+/// assert_eq!(
+///     base_strategy.fish_completion_dir().strip_prefix(&home_dir),
+///     Ok(Path::new(".local/share/fish/vendor_completions.d"))
+/// );
+/// assert_eq!(
+///     base_strategy.man_dir(1).strip_prefix(&home_dir),
+///     Ok(Path::new(".local/share/man/man1"))
+/// );
+/// assert_eq!(
+///     base_strategy.system_bash_completion_dirs(),
+///     [
+///         PathBuf::from("/usr/local/share/bash-completion/completions"),
+///         PathBuf::from("/usr/share/bash-completion/completions"),
+///     ]
+/// );
+/// assert_eq!(
+///     base_strategy.system_man_dirs(8),
+///     [
+///         PathBuf::from("/usr/local/share/man/man8"),
+///         PathBuf::from("/usr/share/man/man8"),
+///     ]
+/// );
+/// ```
+pub trait ShellDirs: BaseStrategy {
+    /// Gets the user’s directory for bash completions.
+    fn bash_completion_dir(&self) -> PathBuf {
+        self.data_dir().join(BASH_COMPLETION_DIR)
+    }
+
+    /// Gets the user’s directory for zsh completions.
+    ///
+    /// Note: zsh doesn’t search this directory by default, so the user may need to add it to their `fpath`.
+    fn zsh_completion_dir(&self) -> PathBuf {
+        self.data_dir().join(ZSH_COMPLETION_DIR)
+    }
+
+    /// Gets the user’s directory for fish completions.
+    fn fish_completion_dir(&self) -> PathBuf {
+        self.data_dir().join(FISH_COMPLETION_DIR)
+    }
+
+    /// Gets the user’s directory for man pages in the given section, e.g. `man1` for section 1.
+    fn man_dir(&self, section: u8) -> PathBuf {
+        self.data_dir().join(man_section_dir(section))
+    }
+
+    /// Gets the system-wide directories for bash completions, in order of decreasing precedence.
+    fn system_bash_completion_dirs(&self) -> Vec<PathBuf> {
+        in_data_dirs(self, BASH_COMPLETION_DIR)
+    }
+
+    /// Gets the system-wide directories for zsh completions, in order of decreasing precedence.
+    fn system_zsh_completion_dirs(&self) -> Vec<PathBuf> {
+        in_data_dirs(self, ZSH_COMPLETION_DIR)
+    }
+
+    /// Gets the system-wide directories for fish completions, in order of decreasing precedence.
+    fn system_fish_completion_dirs(&self) -> Vec<PathBuf> {
+        in_data_dirs(self, FISH_COMPLETION_DIR)
+    }
+
+    /// Gets the system-wide directories for man pages in the given section, in order of decreasing precedence.
+    fn system_man_dirs(&self, section: u8) -> Vec<PathBuf> {
+        in_data_dirs(self, man_section_dir(section))
+    }
+}
+
+impl<T: BaseStrategy + ?Sized> ShellDirs for T {}
+
+const BASH_COMPLETION_DIR: &str = "bash-completion/completions";
+const ZSH_COMPLETION_DIR: &str = "zsh/site-functions";
+const FISH_COMPLETION_DIR: &str = "fish/vendor_completions.d";
+
+fn man_section_dir(section: u8) -> PathBuf {
+    Path::new("man").join(format!("man{section}"))
+}
+
+fn in_data_dirs<S: BaseStrategy + ?Sized>(strategy: &S, path: impl AsRef<Path>) -> Vec<PathBuf> {
+    strategy
+        .data_dirs()
+        .into_iter()
+        .map(|data_dir| data_dir.join(&path))
+        .collect()
+}
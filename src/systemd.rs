@@ -0,0 +1,348 @@
+//! These provide the locations that systemd reads user units and environment variables from, and generate user services for your application.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::app_strategy;
+use crate::base_strategy::{self, BaseStrategy};
+use crate::environment::Environment;
+
+impl<E: Environment> base_strategy::Xdg<E> {
+    /// Gets the user’s directory for systemd user units.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// // We need to conditionally set this to ensure that it is an absolute path both on Windows and other systems.
+    /// let home_path = if cfg!(windows) {
+    ///     "C:\\Users\\frobnicator\\"
+    /// } else {
+    ///     "/home/frobnicator/"
+    /// };
+    ///
+    /// let base_strategy = Xdg::with_env(MapEnvironment::new(home_path)).unwrap();
+    ///
+    /// assert_eq!(
+    ///     base_strategy.systemd_user_dir().strip_prefix(home_path),
+    ///     Ok(Path::new(".config/systemd/user"))
+    /// );
+    /// assert_eq!(
+    ///     base_strategy.system_systemd_user_dirs(),
+    ///     [
+    ///         PathBuf::from("/etc/xdg/systemd/user"),
+    ///         PathBuf::from("/etc/systemd/user"),
+    ///         PathBuf::from("/run/systemd/user"),
+    ///         PathBuf::from("/usr/local/share/systemd/user"),
+    ///         PathBuf::from("/usr/share/systemd/user"),
+    ///         PathBuf::from("/usr/local/lib/systemd/user"),
+    ///         PathBuf::from("/usr/lib/systemd/user"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn systemd_user_dir(&self) -> PathBuf {
+        self.config_dir().join(SYSTEMD_USER_DIR)
+    }
+
+    /// Gets the system-wide directories that systemd reads user units from, in order of decreasing precedence.
+    ///
+    /// These follow the user-independent part of the search path documented in [systemd.unit(5)](https://www.freedesktop.org/software/systemd/man/latest/systemd.unit.html#Unit%20File%20Load%20Path).
+    pub fn system_systemd_user_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = in_dirs(self.config_dirs(), SYSTEMD_USER_DIR);
+        dirs.push(PathBuf::from("/etc/systemd/user"));
+        dirs.push(PathBuf::from("/run/systemd/user"));
+        dirs.extend(in_dirs(self.data_dirs(), SYSTEMD_USER_DIR));
+        dirs.push(PathBuf::from("/usr/local/lib/systemd/user"));
+        dirs.push(PathBuf::from("/usr/lib/systemd/user"));
+        dirs
+    }
+
+    /// Gets the user’s directory for the `.conf` files that set the environment variables of systemd user services.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// // We need to conditionally set this to ensure that it is an absolute path both on Windows and other systems.
+    /// let home_path = if cfg!(windows) {
+    ///     "C:\\Users\\frobnicator\\"
+    /// } else {
+    ///     "/home/frobnicator/"
+    /// };
+    ///
+    /// let base_strategy = Xdg::with_env(MapEnvironment::new(home_path)).unwrap();
+    ///
+    /// assert_eq!(
+    ///     base_strategy.environment_d_dir().strip_prefix(home_path),
+    ///     Ok(Path::new(".config/environment.d"))
+    /// );
+    /// assert_eq!(
+    ///     base_strategy.system_environment_d_dirs(),
+    ///     [
+    ///         PathBuf::from("/etc/environment.d"),
+    ///         PathBuf::from("/run/environment.d"),
+    ///         PathBuf::from("/usr/local/lib/environment.d"),
+    ///         PathBuf::from("/usr/lib/environment.d"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn environment_d_dir(&self) -> PathBuf {
+        self.config_dir().join("environment.d")
+    }
+
+    /// Gets the system-wide directories for the `.conf` files that set the environment variables of systemd user services, in order of decreasing precedence.
+    ///
+    /// These are documented in [environment.d(5)](https://www.freedesktop.org/software/systemd/man/latest/environment.d.html).
+    pub fn system_environment_d_dirs(&self) -> Vec<PathBuf> {
+        vec![
+            PathBuf::from("/etc/environment.d"),
+            PathBuf::from("/run/environment.d"),
+            PathBuf::from("/usr/local/lib/environment.d"),
+            PathBuf::from("/usr/lib/environment.d"),
+        ]
+    }
+}
+
+impl<E: Environment> app_strategy::Xdg<E> {
+    /// Gets the path of your application’s systemd user service, which is named after its ‘[unixy](../app_strategy/struct.AppStrategyArgs.html#method.unixy_name)’ name.
+    pub fn service_unit_path(&self) -> PathBuf {
+        self.base_strategy()
+            .systemd_user_dir()
+            .join(format!("{}.service", self.unixy_name()))
+    }
+
+    /// Generates a systemd user service that runs the given program and arguments.
+    ///
+    /// The service’s environment points the XDG variables at the directories this strategy currently uses, so that your application finds the same directories when it is run by systemd.
+    ///
+    /// ```
+    /// use etcetera::app_strategy::AppStrategyArgs;
+    /// use etcetera::app_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    /// use std::path::Path;
+    ///
+    /// // We need to conditionally set these to ensure that they are absolute paths both on Windows and other systems.
+    /// let home_path = if cfg!(windows) {
+    ///     "C:\\Users\\frobnicator\\"
+    /// } else {
+    ///     "/home/frobnicator/"
+    /// };
+    /// let config_path = if cfg!(windows) {
+    ///     "C:\\config\\"
+    /// } else {
+    ///     "/config/"
+    /// };
+    ///
+    /// let env = MapEnvironment::new(home_path).with_var("XDG_CONFIG_HOME", config_path);
+    ///
+    /// let app_strategy = Xdg::with_env(AppStrategyArgs {
+    ///     top_level_domain: "org".to_string(),
+    ///     author: "Acme Corp".to_string(),
+    ///     app_name: "Frobnicator Plus".to_string(),
+    /// }, env).unwrap();
+    ///
+    /// assert_eq!(
+    ///     app_strategy.service_unit_path(),
+    ///     Path::new(config_path).join("systemd/user/frobnicator-plus.service")
+    /// );
+    ///
+    /// let unit = app_strategy.service_unit(
+    ///     "Frobnicator Plus sync agent",
+    ///     ["/opt/frobnicator plus/frob", "sync"],
+    /// );
+    /// assert_eq!(Path::new(&unit.environment["XDG_CONFIG_HOME"]), Path::new(config_path));
+    /// assert_eq!(
+    ///     Path::new(&unit.environment["XDG_DATA_HOME"]),
+    ///     Path::new(home_path).join(".local/share/")
+    /// );
+    /// ```
+    pub fn service_unit<I, S>(&self, description: impl Into<String>, exec_start: I) -> ServiceUnit
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut unit = ServiceUnit::new(description, exec_start);
+
+        let dirs = [
+            ("XDG_CONFIG_HOME", Some(self.base_strategy().config_dir())),
+            ("XDG_DATA_HOME", Some(self.base_strategy().data_dir())),
+            ("XDG_CACHE_HOME", Some(self.base_strategy().cache_dir())),
+            ("XDG_STATE_HOME", self.base_strategy().state_dir()),
+        ];
+        for (key, dir) in dirs {
+            if let Some(dir) = dir {
+                unit.environment
+                    .insert(key.to_string(), dir.to_string_lossy().into_owned());
+            }
+        }
+
+        unit
+    }
+
+    /// Writes your application’s systemd user service to the [path where it belongs](#method.service_unit_path), creating the directory it lives in if necessary.
+    ///
+    /// Note: systemd only notices the new service after `systemctl --user daemon-reload`.
+    pub fn write_service_unit(&self, unit: &ServiceUnit) -> std::io::Result<()> {
+        let path = self.service_unit_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, unit.to_string())
+    }
+}
+
+/// A basic systemd service unit.
+///
+/// Its [`Display`](https://doc.rust-lang.org/std/fmt/trait.Display.html) implementation produces the contents of a `.service` file, with all values quoted and escaped as systemd requires.
+///
+/// ```
+/// use etcetera::systemd::ServiceUnit;
+///
+/// let mut unit = ServiceUnit::new(
+///     "Frobnicator Plus sync agent",
+///     ["/opt/frobnicator plus/frob", "sync", "--interval=50%"],
+/// );
+/// unit.environment.insert("FROB_LEVEL".to_string(), "11\nExecStartPre=rm".to_string());
+///
+/// assert_eq!(
+///     unit.to_string(),
+///     r#"[Unit]
+/// Description=Frobnicator Plus sync agent
+///
+/// [Service]
+/// ExecStart="/opt/frobnicator plus/frob" sync --interval=50%%
+/// Environment="FROB_LEVEL=11\nExecStartPre=rm"
+///
+/// [Install]
+/// WantedBy=default.target
+/// "#
+/// );
+/// ```
+///
+/// The description and working directory can’t be quoted, so any control characters in them, such as line breaks, are left out:
+///
+/// ```
+/// use etcetera::systemd::ServiceUnit;
+///
+/// let unit = ServiceUnit::new("Frobnicator\nExecStartPre=rm", ["frob"]);
+///
+/// assert!(unit.to_string().contains("Description=FrobnicatorExecStartPre=rm\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ServiceUnit {
+    /// A description of the service.
+    pub description: String,
+    /// The program to run and its arguments, which are quoted and escaped as needed.
+    pub exec_start: Vec<String>,
+    /// The directory the program is run in, if not the home directory.
+    pub working_directory: Option<PathBuf>,
+    /// The environment variables the program is run with.
+    pub environment: BTreeMap<String, String>,
+    /// The targets that pull in the service when it is enabled.
+    pub wanted_by: Vec<String>,
+}
+
+impl ServiceUnit {
+    /// Creates a service unit that runs the given program and arguments, which is started at login when it is enabled.
+    pub fn new<I, S>(description: impl Into<String>, exec_start: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            description: description.into(),
+            exec_start: exec_start.into_iter().map(Into::into).collect(),
+            wanted_by: vec!["default.target".to_string()],
+            ..Self::default()
+        }
+    }
+}
+
+impl fmt::Display for ServiceUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[Unit]")?;
+        writeln!(f, "Description={}", escape_unquoted(&self.description))?;
+        writeln!(f)?;
+
+        writeln!(f, "[Service]")?;
+        let exec_start: Vec<String> = self
+            .exec_start
+            .iter()
+            .map(|arg| quote_exec_arg(arg))
+            .collect();
+        writeln!(f, "ExecStart={}", exec_start.join(" "))?;
+        if let Some(working_directory) = &self.working_directory {
+            writeln!(
+                f,
+                "WorkingDirectory={}",
+                escape_unquoted(&working_directory.to_string_lossy())
+            )?;
+        }
+        for (key, value) in &self.environment {
+            writeln!(
+                f,
+                "Environment={}",
+                quote(&escape_specifiers(&format!("{key}={value}")))
+            )?;
+        }
+
+        if !self.wanted_by.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "[Install]")?;
+            writeln!(f, "WantedBy={}", self.wanted_by.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+const SYSTEMD_USER_DIR: &str = "systemd/user";
+
+fn in_dirs(dirs: Vec<PathBuf>, path: impl AsRef<Path>) -> Vec<PathBuf> {
+    dirs.into_iter().map(|dir| dir.join(&path)).collect()
+}
+
+// systemd expands specifiers such as `%h` in most values.
+fn escape_specifiers(value: &str) -> String {
+    value.replace('%', "%%")
+}
+
+// Values that can’t be quoted end at the first line break, and continue on the next line if they end with a backslash.
+fn escape_unquoted(value: &str) -> String {
+    let value: String = value.chars().filter(|c| !c.is_control()).collect();
+    escape_specifiers(value.trim_end_matches('\\'))
+}
+
+fn quote_exec_arg(arg: &str) -> String {
+    // systemd expands environment variables in the arguments of `ExecStart`.
+    let arg = escape_specifiers(arg).replace('$', "$$");
+    if !arg.is_empty()
+        && !arg
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';'))
+    {
+        return arg;
+    }
+    quote(&arg)
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            // Other control characters would otherwise end the line or be misread.
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
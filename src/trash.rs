@@ -0,0 +1,333 @@
+//! This locates trash directories and moves files into them, as specified by the [FreeDesktop.org Trash Specification](https://specifications.freedesktop.org/trash-spec/latest/).
+
+use std::path::{Path, PathBuf};
+
+use crate::base_strategy::{self, BaseStrategy};
+use crate::environment::Environment;
+
+/// A trash directory, which contains the trashed files in its `files` directory and information about them in its `info` directory.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Trash {
+    path: PathBuf,
+    top_dir: Option<PathBuf>,
+}
+
+impl Trash {
+    /// Gets the path of the trash directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the directory that contains the trashed files.
+    pub fn files_dir(&self) -> PathBuf {
+        self.path.join("files")
+    }
+
+    /// Gets the directory that contains a `.trashinfo` file for each trashed file.
+    pub fn info_dir(&self) -> PathBuf {
+        self.path.join("info")
+    }
+
+    /// Gets the top directory of the mount that this trash directory belongs to, or `None` for the user’s home trash.
+    ///
+    /// The original locations of the files in a top directory’s trash are recorded relative to the top directory.
+    pub fn top_dir(&self) -> Option<&Path> {
+        self.top_dir.as_deref()
+    }
+}
+
+impl<E: Environment> base_strategy::Xdg<E> {
+    /// Gets the user’s home trash, which is the `Trash` directory inside the user’s data directory.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::Xdg;
+    /// use std::path::Path;
+    ///
+    /// let home_dir = Path::new("/home/frobnicator");
+    ///
+    /// let base_strategy = Xdg::with_home(home_dir);
+    ///
+    /// let trash = base_strategy.home_trash();
+    /// assert_eq!(
+    ///     trash.files_dir().strip_prefix(&home_dir),
+    ///     Ok(Path::new(".local/share/Trash/files"))
+    /// );
+    /// assert_eq!(
+    ///     trash.info_dir().strip_prefix(&home_dir),
+    ///     Ok(Path::new(".local/share/Trash/info"))
+    /// );
+    /// assert_eq!(trash.top_dir(), None);
+    /// ```
+    pub fn home_trash(&self) -> Trash {
+        Trash {
+            path: self.data_dir().join("Trash"),
+            top_dir: None,
+        }
+    }
+
+    /// Finds the trash directory that the file or directory at the given path should be moved to.
+    ///
+    /// This is the user’s [home trash](#method.home_trash) if the path is on the same mount as it.
+    /// Otherwise, it is the trash in the top directory of the path’s mount: either `$topdir/.Trash/$uid` if the administrator has set up `$topdir/.Trash` as the specification requires, or `$topdir/.Trash-$uid`.
+    /// The trash directory is created if it doesn’t exist yet.
+    #[cfg(unix)]
+    pub fn trash_for(&self, path: impl AsRef<Path>) -> std::io::Result<Trash> {
+        use std::os::unix::fs::MetadataExt;
+
+        let path = absolute_path(path.as_ref())?;
+        let device = std::fs::symlink_metadata(&path)?.dev();
+
+        let home_trash = self.home_trash();
+        if nearest_existing_ancestor_device(home_trash.path())? == device {
+            return Ok(home_trash);
+        }
+
+        // The top directory is the furthest ancestor that is still on the same mount.
+        let mut top_dir = path.as_path();
+        while let Some(parent) = top_dir.parent() {
+            if std::fs::metadata(parent)?.dev() != device {
+                break;
+            }
+            top_dir = parent;
+        }
+
+        // SAFETY: `getuid` is always successful and has no preconditions.
+        let uid = unsafe { libc::getuid() };
+
+        let admin_trash = top_dir.join(".Trash");
+        if is_valid_admin_trash(&admin_trash) {
+            let path = admin_trash.join(uid.to_string());
+            if create_private_dir(&path).is_ok() {
+                return Ok(Trash {
+                    path,
+                    top_dir: Some(top_dir.to_path_buf()),
+                });
+            }
+        }
+
+        let path = top_dir.join(format!(".Trash-{uid}"));
+        create_private_dir(&path)?;
+        Ok(Trash {
+            path,
+            top_dir: Some(top_dir.to_path_buf()),
+        })
+    }
+
+    /// Moves the file or directory at the given path to the [trash directory it belongs in](#method.trash_for), and returns its new path.
+    ///
+    /// A `.trashinfo` file that records where it came from and when it was deleted is written alongside it, so that it can be restored.
+    /// If the trash already contains something with the same name, a number is appended to the name.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    ///
+    /// # if cfg!(miri) { return; } // Miri can’t look up the current user or the local time.
+    /// let temp_dir = std::env::temp_dir().join("etcetera-move-to-trash");
+    /// std::fs::create_dir_all(&temp_dir).unwrap();
+    ///
+    /// let env = MapEnvironment::new(&temp_dir).with_var("XDG_DATA_HOME", temp_dir.join("data"));
+    ///
+    /// let base_strategy = Xdg::with_env(env).unwrap();
+    ///
+    /// let file = temp_dir.join("100% done.txt");
+    /// std::fs::write(&file, "").unwrap();
+    /// let trashed_file = base_strategy.move_to_trash(&file).unwrap();
+    ///
+    /// let trash = base_strategy.home_trash();
+    /// assert!(!file.exists());
+    /// assert_eq!(trashed_file, trash.files_dir().join("100% done.txt"));
+    ///
+    /// let info = std::fs::read_to_string(trash.info_dir().join("100% done.txt.trashinfo")).unwrap();
+    /// let mut lines = info.lines();
+    /// assert_eq!(lines.next(), Some("[Trash Info]"));
+    /// assert_eq!(
+    ///     lines.next().unwrap(),
+    ///     format!("Path={}/100%25%20done.txt", temp_dir.canonicalize().unwrap().display())
+    /// );
+    /// assert!(lines.next().unwrap().starts_with("DeletionDate="));
+    ///
+    /// // A second file with the same name gets a different name in the trash.
+    /// std::fs::write(&file, "").unwrap();
+    /// assert_eq!(
+    ///     base_strategy.move_to_trash(&file).unwrap(),
+    ///     trash.files_dir().join("100% done.txt.2")
+    /// );
+    ///
+    /// std::fs::remove_dir_all(temp_dir).unwrap();
+    /// ```
+    #[cfg(unix)]
+    pub fn move_to_trash(&self, path: impl AsRef<Path>) -> std::io::Result<PathBuf> {
+        use std::io::Write;
+
+        let path = absolute_path(path.as_ref())?;
+        let trash = self.trash_for(&path)?;
+        let file_name = path.file_name().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "cannot move a path without a file name to the trash",
+            )
+        })?;
+
+        if let Some(parent) = trash.path().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        create_private_dir(trash.path())?;
+        let files_dir = trash.files_dir();
+        let info_dir = trash.info_dir();
+        create_private_dir(&files_dir)?;
+        create_private_dir(&info_dir)?;
+
+        let original_path = match trash.top_dir() {
+            Some(top_dir) => path.strip_prefix(top_dir).unwrap_or(&path),
+            None => &path,
+        };
+        let info = format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            percent_encode(original_path),
+            local_timestamp()
+        );
+
+        for n in 1.. {
+            let mut name = file_name.to_os_string();
+            if n > 1 {
+                name.push(format!(".{n}"));
+            }
+            let mut info_name = name.clone();
+            info_name.push(".trashinfo");
+
+            // Creating the info file atomically reserves the name, as the specification requires.
+            let info_path = info_dir.join(info_name);
+            let mut info_file = match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&info_path)
+            {
+                Ok(info_file) => info_file,
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            };
+
+            let trashed_path = files_dir.join(name);
+            if std::fs::symlink_metadata(&trashed_path).is_ok() {
+                // A file without an info file is in the way, so this name can’t be used.
+                std::fs::remove_file(&info_path)?;
+                continue;
+            }
+
+            let result = info_file
+                .write_all(info.as_bytes())
+                .and_then(|()| std::fs::rename(&path, &trashed_path));
+            if let Err(err) = result {
+                let _ = std::fs::remove_file(&info_path);
+                return Err(err);
+            }
+
+            return Ok(trashed_path);
+        }
+
+        unreachable!()
+    }
+}
+
+#[cfg(unix)]
+fn absolute_path(path: &Path) -> std::io::Result<PathBuf> {
+    // Only the parent is canonicalized, so that a symbolic link is trashed rather than what it points to.
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(file_name)) if !parent.as_os_str().is_empty() => {
+            Ok(parent.canonicalize()?.join(file_name))
+        }
+        (_, Some(file_name)) => Ok(std::env::current_dir()?.join(file_name)),
+        _ => path.canonicalize(),
+    }
+}
+
+#[cfg(unix)]
+fn nearest_existing_ancestor_device(path: &Path) -> std::io::Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut ancestors = path.ancestors();
+    loop {
+        match ancestors.next().map(std::fs::metadata) {
+            Some(Ok(metadata)) => return Ok(metadata.dev()),
+            Some(Err(err)) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Some(Err(err)) => return Err(err),
+            None => return Err(std::io::ErrorKind::NotFound.into()),
+        }
+    }
+}
+
+// `$topdir/.Trash` may only be used if it is a real directory with the sticky bit set.
+#[cfg(unix)]
+fn is_valid_admin_trash(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::symlink_metadata(path)
+        .is_ok_and(|metadata| metadata.is_dir() && metadata.permissions().mode() & 0o1000 != 0)
+}
+
+// Creates a directory only the current user can access, or checks that an existing one is
+// owned by the current user.
+#[cfg(unix)]
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    match std::fs::DirBuilder::new().mode(0o700).create(path) {
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+        result => return result,
+    }
+
+    let metadata = std::fs::symlink_metadata(path)?;
+    // SAFETY: `getuid` is always successful and has no preconditions.
+    let uid = unsafe { libc::getuid() };
+    if !metadata.is_dir() || metadata.uid() != uid {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!(
+                "{} is not a directory owned by the current user",
+                path.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// Escapes a path the way URLs are escaped, which is how the specification requires paths to be stored.
+#[cfg(unix)]
+fn percent_encode(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut encoded = String::new();
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.!~*'()/".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+// Formats the current time in the local time zone as `YYYY-MM-DDThh:mm:ss`.
+#[cfg(unix)]
+fn local_timestamp() -> String {
+    // SAFETY: `time` accepts a null pointer, and `localtime_r` only writes to the `tm` it is given,
+    // which may be zeroed as it only contains integers and, on some platforms, a pointer that is
+    // allowed to be null.
+    let tm = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        libc::localtime_r(&now, &mut tm);
+        tm
+    };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec
+    )
+}
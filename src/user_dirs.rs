@@ -0,0 +1,489 @@
+//! These provide the locations of the user’s well-known folders, such as their desktop, documents, and downloads.
+
+use std::path::{Path, PathBuf};
+
+use crate::base_strategy::{self, BaseStrategy};
+use crate::environment::Environment;
+
+/// One of the user’s well-known folders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UserDir {
+    /// The desktop folder.
+    Desktop,
+    /// The documents folder.
+    Documents,
+    /// The downloads folder.
+    Download,
+    /// The music folder.
+    Music,
+    /// The pictures folder.
+    Pictures,
+    /// The public folder, whose contents are shared with other users.
+    PublicShare,
+    /// The templates folder, containing templates for new documents.
+    Templates,
+    /// The videos folder.
+    Videos,
+}
+
+impl UserDir {
+    /// All of the user’s well-known folders.
+    pub const ALL: [UserDir; 8] = [
+        Self::Desktop,
+        Self::Documents,
+        Self::Download,
+        Self::Music,
+        Self::Pictures,
+        Self::PublicShare,
+        Self::Templates,
+        Self::Videos,
+    ];
+
+    /// The name of the variable that holds this folder in `user-dirs.dirs`, e.g. `XDG_DOWNLOAD_DIR`.
+    pub fn xdg_key(self) -> &'static str {
+        match self {
+            Self::Desktop => "XDG_DESKTOP_DIR",
+            Self::Documents => "XDG_DOCUMENTS_DIR",
+            Self::Download => "XDG_DOWNLOAD_DIR",
+            Self::Music => "XDG_MUSIC_DIR",
+            Self::Pictures => "XDG_PICTURES_DIR",
+            Self::PublicShare => "XDG_PUBLICSHARE_DIR",
+            Self::Templates => "XDG_TEMPLATES_DIR",
+            Self::Videos => "XDG_VIDEOS_DIR",
+        }
+    }
+
+    // The untranslated names used by `xdg-user-dirs-update`.
+    fn xdg_default_name(self) -> &'static str {
+        match self {
+            Self::Desktop => "Desktop",
+            Self::Documents => "Documents",
+            Self::Download => "Downloads",
+            Self::Music => "Music",
+            Self::Pictures => "Pictures",
+            Self::PublicShare => "Public",
+            Self::Templates => "Templates",
+            Self::Videos => "Videos",
+        }
+    }
+}
+
+/// Provides the locations of the user’s well-known folders.
+///
+/// The [`Xdg`](../base_strategy/struct.Xdg.html) strategy reads these from `user-dirs.dirs` inside the user’s configuration directory, as maintained by [xdg-user-dirs](https://www.freedesktop.org/wiki/Software/xdg-user-dirs/).
+/// This means that they follow the user’s choices, including folder names in the user’s language.
+/// The folders that aren’t listed in that file default to their English names inside the home directory.
+///
+/// ```
+/// use etcetera::base_strategy::Xdg;
+/// use etcetera::environment::MapEnvironment;
+/// use etcetera::user_dirs::UserDirs;
+/// use std::path::Path;
+///
+/// // We need to conditionally set this to ensure that it is an absolute path both on Windows and other systems.
+/// let music_path = if cfg!(windows) {
+///     "C:\\media\\music"
+/// } else {
+///     "/media/music"
+/// };
+///
+/// let config_home = std::env::temp_dir().join("etcetera-user-dirs");
+/// std::fs::create_dir_all(&config_home).unwrap();
+/// std::fs::write(
+///     config_home.join("user-dirs.dirs"),
+///     format!(r#"
+/// # This file is written by xdg-user-dirs-update
+/// XDG_DESKTOP_DIR="$HOME/Schreibtisch"
+/// XDG_DOWNLOAD_DIR="$HOME/Téléchargements"
+/// XDG_MUSIC_DIR="{music_path}"
+/// XDG_PICTURES_DIR="$HOME/My \"Pictures\""
+/// XDG_PUBLICSHARE_DIR="$HOME/"
+/// XDG_VIDEOS_DIR=relative/path
+/// "#),
+/// ).unwrap();
+///
+/// let home_dir = Path::new("/home/frobnicator");
+///
+/// let env = MapEnvironment::new(home_dir).with_var("XDG_CONFIG_HOME", &config_home);
+///
+/// let base_strategy = Xdg::with_env(env).unwrap();
+///
+/// assert_eq!(base_strategy.desktop_dir(), Some(home_dir.join("Schreibtisch")));
+/// assert_eq!(base_strategy.download_dir(), Some(home_dir.join("Téléchargements")));
+/// assert_eq!(base_strategy.music_dir(), Some(music_path.into()));
+/// assert_eq!(base_strategy.pictures_dir(), Some(home_dir.join("My \"Pictures\"")));
+///
+/// // Pointing a folder at the home directory disables it.
+/// assert_eq!(base_strategy.public_dir(), None);
+///
+/// // Relative paths are ignored.
+/// assert_eq!(base_strategy.videos_dir(), None);
+///
+/// // Folders that aren’t listed get their default location.
+/// assert_eq!(base_strategy.documents_dir(), Some(home_dir.join("Documents")));
+///
+/// std::fs::remove_dir_all(config_home).unwrap();
+/// ```
+///
+/// The [`Apple`](../base_strategy/struct.Apple.html) and [`Windows`](../base_strategy/struct.Windows.html) strategies return the usual folders inside the home directory.
+///
+/// ```
+/// use etcetera::base_strategy::Apple;
+/// use etcetera::user_dirs::UserDirs;
+/// use std::path::Path;
+///
+/// let home_dir = Path::new("/Users/frobnicator");
+///
+/// let base_strategy = Apple::with_home(home_dir);
+///
+/// assert_eq!(base_strategy.download_dir(), Some(home_dir.join("Downloads")));
+/// assert_eq!(base_strategy.videos_dir(), Some(home_dir.join("Movies")));
+/// assert_eq!(base_strategy.templates_dir(), None);
+/// ```
+pub trait UserDirs: BaseStrategy {
+    /// Gets the location of one of the user’s well-known folders, or `None` if the user doesn’t have that folder.
+    fn user_dir(&self, dir: UserDir) -> Option<PathBuf>;
+
+    /// Gets the user’s desktop folder.
+    fn desktop_dir(&self) -> Option<PathBuf> {
+        self.user_dir(UserDir::Desktop)
+    }
+
+    /// Gets the user’s documents folder.
+    fn documents_dir(&self) -> Option<PathBuf> {
+        self.user_dir(UserDir::Documents)
+    }
+
+    /// Gets the user’s downloads folder.
+    fn download_dir(&self) -> Option<PathBuf> {
+        self.user_dir(UserDir::Download)
+    }
+
+    /// Gets the user’s music folder.
+    fn music_dir(&self) -> Option<PathBuf> {
+        self.user_dir(UserDir::Music)
+    }
+
+    /// Gets the user’s pictures folder.
+    fn pictures_dir(&self) -> Option<PathBuf> {
+        self.user_dir(UserDir::Pictures)
+    }
+
+    /// Gets the user’s public folder.
+    fn public_dir(&self) -> Option<PathBuf> {
+        self.user_dir(UserDir::PublicShare)
+    }
+
+    /// Gets the user’s templates folder.
+    fn templates_dir(&self) -> Option<PathBuf> {
+        self.user_dir(UserDir::Templates)
+    }
+
+    /// Gets the user’s videos folder.
+    fn videos_dir(&self) -> Option<PathBuf> {
+        self.user_dir(UserDir::Videos)
+    }
+}
+
+impl<E: Environment> UserDirs for base_strategy::Xdg<E> {
+    fn user_dir(&self, dir: UserDir) -> Option<PathBuf> {
+        let contents = std::fs::read(self.config_dir().join("user-dirs.dirs")).unwrap_or_default();
+        let contents = String::from_utf8_lossy(&contents);
+
+        // Later assignments override earlier ones, just like they would in a shell.
+        let value = contents
+            .lines()
+            .rev()
+            .filter_map(parse_line)
+            .find(|(key, _)| *key == dir.xdg_key());
+
+        match value {
+            Some((_, value)) => {
+                let path = value.into_path(self.home_dir())?;
+                // A folder that points to the home directory has been disabled.
+                if path == self.home_dir() {
+                    None
+                } else {
+                    Some(path)
+                }
+            }
+            None => Some(self.home_dir().join(dir.xdg_default_name())),
+        }
+    }
+}
+
+impl<E: Environment> UserDirs for base_strategy::Apple<E> {
+    fn user_dir(&self, dir: UserDir) -> Option<PathBuf> {
+        let name = match dir {
+            UserDir::Desktop => "Desktop",
+            UserDir::Documents => "Documents",
+            UserDir::Download => "Downloads",
+            UserDir::Music => "Music",
+            UserDir::Pictures => "Pictures",
+            UserDir::PublicShare => "Public",
+            UserDir::Templates => return None,
+            UserDir::Videos => "Movies",
+        };
+        Some(self.home_dir().join(name))
+    }
+}
+
+impl<E: Environment> UserDirs for base_strategy::Windows<E> {
+    fn user_dir(&self, dir: UserDir) -> Option<PathBuf> {
+        let name = match dir {
+            UserDir::Desktop => "Desktop",
+            UserDir::Documents => "Documents",
+            UserDir::Download => "Downloads",
+            UserDir::Music => "Music",
+            UserDir::Pictures => "Pictures",
+            // The public folder is shared by all users, so it doesn’t live inside the home directory.
+            UserDir::PublicShare => return None,
+            UserDir::Templates => {
+                return Some(
+                    self.data_dir()
+                        .join("Microsoft")
+                        .join("Windows")
+                        .join("Templates"),
+                );
+            }
+            UserDir::Videos => "Videos",
+        };
+        Some(self.home_dir().join(name))
+    }
+}
+
+impl<E: Environment> base_strategy::Xdg<E> {
+    /// Changes the location of one of the user’s well-known folders, like `xdg-user-dirs-update --set` does.
+    ///
+    /// This rewrites the folder’s entry in `user-dirs.dirs` inside the user’s configuration directory, keeping the file’s other lines, including comments and unknown entries, exactly as they were.
+    /// Paths inside the home directory are written relative to `$HOME`.
+    /// The file and the configuration directory are created if they don’t exist yet.
+    ///
+    /// The folder itself isn’t created or moved.
+    ///
+    /// ```
+    /// use etcetera::base_strategy::Xdg;
+    /// use etcetera::environment::MapEnvironment;
+    /// use etcetera::user_dirs::{UserDir, UserDirs};
+    ///
+    /// // We need to conditionally set this to ensure that it is an absolute path both on Windows and other systems.
+    /// // On Windows, its backslashes are escaped in `user-dirs.dirs`.
+    /// let (music_path, quoted_music_path) = if cfg!(windows) {
+    ///     ("C:\\media\\music", "C:\\\\media\\\\music")
+    /// } else {
+    ///     ("/media/music", "/media/music")
+    /// };
+    ///
+    /// let home_dir = std::env::temp_dir().join("etcetera-set-user-dir");
+    /// let config_home = home_dir.join(".config");
+    /// std::fs::create_dir_all(&config_home).unwrap();
+    /// std::fs::write(
+    ///     config_home.join("user-dirs.dirs"),
+    ///     "# A comment\nXDG_DOWNLOAD_DIR=\"$HOME/Downloads\"\nXDG_CUSTOM_DIR=\"/custom\"\n",
+    /// ).unwrap();
+    ///
+    /// let base_strategy = Xdg::with_env(MapEnvironment::new(&home_dir)).unwrap();
+    ///
+    /// base_strategy.set_user_dir(UserDir::Download, home_dir.join("Incoming")).unwrap();
+    /// base_strategy.set_user_dir(UserDir::Music, music_path).unwrap();
+    ///
+    /// assert_eq!(base_strategy.download_dir(), Some(home_dir.join("Incoming")));
+    /// assert_eq!(base_strategy.music_dir(), Some(music_path.into()));
+    /// assert_eq!(
+    ///     std::fs::read_to_string(config_home.join("user-dirs.dirs")).unwrap(),
+    ///     format!("# A comment\nXDG_DOWNLOAD_DIR=\"$HOME/Incoming\"\nXDG_CUSTOM_DIR=\"/custom\"\nXDG_MUSIC_DIR=\"{quoted_music_path}\"\n"),
+    /// );
+    ///
+    /// // Line endings and lines that aren’t valid UTF-8 are left alone.
+    /// std::fs::write(
+    ///     config_home.join("user-dirs.dirs"),
+    ///     b"XDG_MUSIC_DIR=\"$HOME/M\xfcsik\"\r\nXDG_DOWNLOAD_DIR=\"$HOME/Downloads\"\r\n",
+    /// ).unwrap();
+    /// base_strategy.set_user_dir(UserDir::Download, home_dir.join("Incoming")).unwrap();
+    /// assert_eq!(
+    ///     std::fs::read(config_home.join("user-dirs.dirs")).unwrap(),
+    ///     b"XDG_MUSIC_DIR=\"$HOME/M\xfcsik\"\r\nXDG_DOWNLOAD_DIR=\"$HOME/Incoming\"\r\n",
+    /// );
+    ///
+    /// std::fs::remove_dir_all(home_dir).unwrap();
+    /// ```
+    pub fn set_user_dir(&self, dir: UserDir, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        if !path.is_absolute() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "user directory must be an absolute path",
+            ));
+        }
+
+        let value = match path.strip_prefix(self.home_dir()) {
+            Ok(relative_path) => format!("$HOME/{}", quote(relative_path)?),
+            Err(_) => quote(path)?,
+        };
+        let assignment = format!("{}=\"{value}\"", dir.xdg_key());
+
+        let config_dir = self.config_dir();
+        let file_path = config_dir.join("user-dirs.dirs");
+        // The file is handled as bytes, so that the lines that aren’t replaced are kept exactly as they were, even if they aren’t valid UTF-8.
+        let contents = match std::fs::read(&file_path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        let line_ending: &[u8] = if contents.windows(2).any(|pair| pair == b"\r\n") {
+            b"\r\n"
+        } else {
+            b"\n"
+        };
+
+        let mut replaced = false;
+        let mut new_contents = Vec::with_capacity(contents.len() + assignment.len() + 2);
+        for line in contents.split_inclusive(|&byte| byte == b'\n') {
+            let content_len = line
+                .iter()
+                .rposition(|&byte| byte != b'\n' && byte != b'\r')
+                .map_or(0, |i| i + 1);
+            let (content, ending) = line.split_at(content_len);
+            match parse_line(&String::from_utf8_lossy(content)) {
+                Some((key, _)) if key == dir.xdg_key() => {
+                    // Any later duplicates would override the new value, so they are dropped.
+                    if !replaced {
+                        new_contents.extend_from_slice(assignment.as_bytes());
+                        new_contents.extend_from_slice(if ending.is_empty() {
+                            line_ending
+                        } else {
+                            ending
+                        });
+                        replaced = true;
+                    }
+                }
+                _ => new_contents.extend_from_slice(line),
+            }
+        }
+        if !replaced {
+            if !new_contents.is_empty() && !new_contents.ends_with(b"\n") {
+                new_contents.extend_from_slice(line_ending);
+            }
+            new_contents.extend_from_slice(assignment.as_bytes());
+            new_contents.extend_from_slice(line_ending);
+        }
+
+        // Writing to a temporary file first means the file is never left half-written.
+        std::fs::create_dir_all(&config_dir)?;
+        let temp_file_path = config_dir.join("user-dirs.dirs.tmp");
+        std::fs::write(&temp_file_path, new_contents)?;
+        std::fs::rename(temp_file_path, file_path)
+    }
+}
+
+// Escapes a path so that it can be placed inside double quotes.
+fn quote(path: &Path) -> std::io::Result<String> {
+    let path = path.to_str().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "user directory must be valid UTF-8",
+        )
+    })?;
+
+    let mut quoted = String::with_capacity(path.len());
+    for c in path.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    Ok(quoted)
+}
+
+/// A value from `user-dirs.dirs`, with its quoting removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Value {
+    // Whether the value starts with `$HOME`, which has been stripped from `rest`.
+    home_relative: bool,
+    rest: String,
+}
+
+impl Value {
+    fn into_path(self, home_dir: &Path) -> Option<PathBuf> {
+        if self.home_relative {
+            Some(home_dir.join(self.rest.trim_start_matches('/')))
+        } else {
+            // Like `xdg-user-dirs`, only absolute paths are accepted.
+            let path = PathBuf::from(self.rest);
+            if path.is_absolute() { Some(path) } else { None }
+        }
+    }
+}
+
+/// Parses a line of `user-dirs.dirs`, which is a shell script that only contains variable assignments.
+fn parse_line(line: &str) -> Option<(&str, Value)> {
+    let line = line.trim_start();
+    if line.starts_with('#') {
+        return None;
+    }
+
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim_end();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    Some((key, unquote(value.trim_start())?))
+}
+
+fn unquote(value: &str) -> Option<Value> {
+    let mut home_relative = false;
+    let mut rest = String::new();
+    let mut chars = value.chars();
+    let mut in_double_quotes = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => in_double_quotes = !in_double_quotes,
+            '\'' if !in_double_quotes => loop {
+                match chars.next()? {
+                    '\'' => break,
+                    c => rest.push(c),
+                }
+            },
+            '\\' => match chars.next()? {
+                // Inside double quotes, a backslash only escapes characters that are special there.
+                c @ ('"' | '\\' | '$' | '`') if in_double_quotes => rest.push(c),
+                c if in_double_quotes => {
+                    rest.push('\\');
+                    rest.push(c);
+                }
+                c => rest.push(c),
+            },
+            '$' if rest.is_empty() && !home_relative => {
+                let remaining = chars.clone().collect::<String>();
+                let len = if remaining.starts_with("{HOME}") {
+                    "{HOME}".len()
+                } else if remaining.starts_with("HOME")
+                    && !remaining[4..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    "HOME".len()
+                } else {
+                    // Other variables aren’t supported.
+                    return None;
+                };
+                home_relative = true;
+                for _ in 0..len {
+                    chars.next();
+                }
+            }
+            // Unquoted whitespace ends the value, and may be followed by a comment.
+            c if c.is_whitespace() && !in_double_quotes => break,
+            c => rest.push(c),
+        }
+    }
+
+    if in_double_quotes {
+        return None;
+    }
+
+    Some(Value {
+        home_relative,
+        rest,
+    })
+}